@@ -0,0 +1,309 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::{catalog, download, fsutil, game, manifest, paths, profiles, saves, settings};
+
+const ARCHIVE_DIRNAME: &str = "archive";
+const ENTRY_METADATA_FILENAME: &str = "entry.json";
+
+// Number of previous versions kept per mod for rollbacks.
+const VERSIONS_PER_MOD: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ArchivedVersion {
+    pub mod_id: String,
+    pub version: String,
+    pub filename: String,
+    // Unix timestamp (seconds) of when this version was replaced.
+    pub archived_at: u64,
+    pub size: u64,
+    // Directory holding this version inside the mod's archive folder.
+    #[serde(skip)]
+    pub dir: String,
+}
+
+#[derive(Serialize)]
+pub struct RollbackResult {
+    pub mod_id: String,
+    pub version: String,
+    pub filename: String,
+    // The version that was installed before the rollback, now archived.
+    pub replaced: Option<ArchivedVersion>,
+}
+
+// Mod ids and versions end up as directory names; keep them portable.
+fn sanitize_component(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') { c } else { '_' })
+        .collect();
+    match cleaned.trim_matches('.') {
+        "" => "_".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+fn mod_archive_dir(mod_id: &str) -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(ARCHIVE_DIRNAME).join(sanitize_component(mod_id)))
+}
+
+fn read_versions(mod_dir: &Path) -> Vec<ArchivedVersion> {
+    let Ok(dirs) = fs::read_dir(mod_dir) else {
+        return Vec::new();
+    };
+    let mut versions: Vec<ArchivedVersion> = dirs
+        .flatten()
+        .filter_map(|dir| {
+            let name = dir.file_name().to_string_lossy().to_string();
+            // Dot directories are swaps in progress.
+            if name.starts_with('.') {
+                return None;
+            }
+            let content = fs::read_to_string(dir.path().join(ENTRY_METADATA_FILENAME)).ok()?;
+            let version: ArchivedVersion = serde_json::from_str(&content).ok()?;
+            Some(ArchivedVersion { dir: name, ..version })
+        })
+        .collect();
+    // Newest first
+    versions.sort_by_key(|v| std::cmp::Reverse(v.archived_at));
+    versions
+}
+
+// Drop versions beyond the retention limit, never touching `keep`.
+fn prune(mod_dir: &Path, keep: Option<&str>) {
+    let versions = read_versions(mod_dir);
+    let mut retained = 0;
+    for version in versions {
+        if Some(version.version.as_str()) == keep {
+            continue;
+        }
+        retained += 1;
+        if retained > VERSIONS_PER_MOD {
+            let dir = mod_dir.join(&version.dir);
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                eprintln!("Failed to prune archived version {:?}: {}", dir, e);
+            }
+        }
+    }
+}
+
+// Directory for `version`: the one already holding it, else the first
+// free one. Different versions can sanitize to the same name, e.g. "1.0+a"
+// and "1.0 a", so those get a numbered suffix.
+fn version_dir_name(mod_dir: &Path, version: &str) -> String {
+    let existing = read_versions(mod_dir);
+    let base = sanitize_component(version);
+    (1..)
+        .map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+        .find(|name| match existing.iter().find(|v| v.dir == *name) {
+            Some(archived) => archived.version == version,
+            None => !mod_dir.join(name).exists(),
+        })
+        .unwrap_or(base)
+}
+
+fn archive_file_keeping(
+    path: &Path,
+    mod_id: &str,
+    version: &str,
+    keep: Option<&str>,
+) -> io::Result<ArchivedVersion> {
+    let mod_dir = mod_archive_dir(mod_id).map_err(io::Error::other)?;
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| io::Error::other(format!("{} has no file name", path.display())))?;
+    let size = fs::metadata(path)?.len();
+    let dir = version_dir_name(&mod_dir, version);
+
+    let archived = ArchivedVersion {
+        mod_id: mod_id.to_string(),
+        version: version.to_string(),
+        filename,
+        archived_at: fsutil::unix_now(),
+        size,
+        dir: dir.clone(),
+    };
+
+    // Fill a staging directory first so an older copy of the same version
+    // is only replaced once the new one is complete.
+    let staging = mod_dir.join(format!(".{}.partial", dir));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging)?;
+    let staged = serde_json::to_string_pretty(&archived)
+        .map_err(io::Error::other)
+        .and_then(|metadata| fs::write(staging.join(ENTRY_METADATA_FILENAME), metadata))
+        .and_then(|_| fsutil::move_file(path, &staging.join(&archived.filename)));
+    if let Err(e) = staged {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(e);
+    }
+
+    let version_dir = mod_dir.join(&dir);
+    let previous = mod_dir.join(format!(".{}.old", dir));
+    let had_previous = version_dir.exists();
+    let swapped = if had_previous {
+        let _ = fs::remove_dir_all(&previous);
+        fs::rename(&version_dir, &previous)
+    } else {
+        Ok(())
+    }
+    .and_then(|_| fs::rename(&staging, &version_dir));
+    if let Err(e) = swapped {
+        // Put the file back where it came from and keep the older copy.
+        let _ = fsutil::move_file(&staging.join(&archived.filename), path);
+        let _ = fs::remove_dir_all(&staging);
+        if had_previous && !version_dir.exists() {
+            let _ = fs::rename(&previous, &version_dir);
+        }
+        return Err(e);
+    }
+    let _ = fs::remove_dir_all(&previous);
+
+    prune(&mod_dir, keep);
+    Ok(archived)
+}
+
+// Move a replaced mod zip into the archive so it can be rolled back to.
+pub fn archive_file(path: &Path, mod_id: &str, version: &str) -> io::Result<ArchivedVersion> {
+    archive_file_keeping(path, mod_id, version, None)
+}
+
+// Undo `archive_file`: move the file back to `to` and drop its version
+// directory.
+pub fn unarchive(archived: &ArchivedVersion, to: &Path) -> io::Result<()> {
+    let archived_dir = mod_archive_dir(&archived.mod_id).map_err(io::Error::other)?.join(&archived.dir);
+    fsutil::move_file(&archived_dir.join(&archived.filename), to)?;
+    fs::remove_dir_all(&archived_dir)
+}
+
+#[tauri::command]
+pub fn list_archived_versions(mod_id: Option<String>) -> Result<Vec<ArchivedVersion>, String> {
+    if let Some(mod_id) = mod_id {
+        return Ok(read_versions(&mod_archive_dir(&mod_id)?));
+    }
+
+    let root = paths::data_dir()?.join(ARCHIVE_DIRNAME);
+    let Ok(dirs) = fs::read_dir(&root) else {
+        return Ok(Vec::new());
+    };
+    Ok(dirs.flatten().flat_map(|dir| read_versions(&dir.path())).collect())
+}
+
+// Swap the installed file of `mod_id` for an archived `version` and point
+// the manifest at it. Any step that fails undoes the previous ones so the
+// mods folder and the manifest never disagree. A mod parked in the
+// inactive store by a profile is rolled back there and stays disabled.
+#[tauri::command]
+pub async fn rollback_mod(mod_id: String, version: String) -> Result<RollbackResult, String> {
+    game::ensure_game_not_running()?;
+    fsutil::blocking(move || rollback(mod_id, &version)).await
+}
+
+fn rollback(mod_id: String, version: &str) -> Result<RollbackResult, String> {
+    let mods_folder = settings::mods_folder()?;
+    let inactive = profiles::inactive_dir()?;
+    let mod_dir = mod_archive_dir(&mod_id)?;
+    let target = read_versions(&mod_dir)
+        .into_iter()
+        .find(|v| v.version == version)
+        .ok_or_else(|| format!("No archived version {} of {}", version, mod_id))?;
+    // entry.json is on disk and could have been edited.
+    if download::safe_filename(&target.filename) != Some(target.filename.as_str()) {
+        return Err(format!("Archived version {} of {} has an invalid file name", version, mod_id));
+    }
+    let target_dir = mod_dir.join(&target.dir);
+
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let current = installed
+        .mods
+        .get(&mod_id)
+        .map(|entry| (entry.filename.clone(), entry.version.clone()));
+
+    // Nothing to do, and archiving the installed file would replace the
+    // very copy we are rolling back to.
+    if let Some((filename, current_version)) = &current {
+        if *current_version == target.version {
+            return Ok(RollbackResult {
+                mod_id,
+                version: target.version,
+                filename: filename.clone(),
+                replaced: None,
+            });
+        }
+    }
+
+    // Career saves may not survive another version of a core mod.
+    if let (Some((_, current_version)), Some(config)) = (&current, catalog::find_loaded(&mod_id)) {
+        if config.category.as_deref() == Some("core") {
+            saves::backup_before_update(&config.name, current_version, &target.version)?;
+        }
+    }
+
+    // The folder the installed copy is in; the rolled back version goes there.
+    let folder = match &current {
+        Some((filename, _)) if !mods_folder.join(filename).exists() && inactive.join(filename).exists() => inactive,
+        _ => mods_folder,
+    };
+    let target_dest = folder.join(&target.filename);
+
+    // 1. Archive the currently installed version, if it is on disk.
+    let replaced = match &current {
+        Some((filename, current_version)) if folder.join(filename).exists() => {
+            let archived = archive_file_keeping(&folder.join(filename), &mod_id, current_version, Some(&target.version))
+                .map_err(|e| format!("Failed to archive installed {}: {}", filename, e))?;
+            Some(archived)
+        }
+        _ => None,
+    };
+
+    let undo_archive = |replaced: &Option<ArchivedVersion>| {
+        if let Some(archived) = replaced {
+            if let Err(e) = unarchive(archived, &folder.join(&archived.filename)) {
+                eprintln!("Failed to restore {} after rollback error: {}", archived.filename, e);
+            }
+        }
+    };
+
+    // 2. Put the archived version in place.
+    if target_dest.exists() {
+        undo_archive(&replaced);
+        return Err(format!("Cannot roll back: {} already exists in {}", target.filename, folder.display()));
+    }
+    if let Err(e) = fs::create_dir_all(&folder).and_then(|_| fsutil::move_file(&target_dir.join(&target.filename), &target_dest)) {
+        undo_archive(&replaced);
+        return Err(format!("Failed to restore {}: {}", target.filename, e));
+    }
+
+    // 3. Point the manifest at the restored file.
+    let updated = manifest::update(|installed| {
+        let entry = installed.mods.entry(mod_id.clone()).or_default();
+        entry.version = target.version.clone();
+        entry.filename = target.filename.clone();
+        entry.size = Some(target.size);
+        entry.sha256 = None;
+        entry.installed_at = Some(fsutil::unix_now());
+        Ok(())
+    });
+    if let Err(e) = updated {
+        if let Err(move_err) = fsutil::move_file(&target_dest, &target_dir.join(&target.filename)) {
+            eprintln!("Failed to move {} back to the archive: {}", target.filename, move_err);
+        }
+        undo_archive(&replaced);
+        return Err(format!("Failed to update manifest: {}", e));
+    }
+
+    if let Err(e) = fs::remove_dir_all(&target_dir) {
+        eprintln!("Failed to clean up archived version {:?}: {}", target_dir, e);
+    }
+    prune(&mod_dir, None);
+
+    Ok(RollbackResult {
+        mod_id,
+        version: target.version,
+        filename: target.filename,
+        replaced,
+    })
+}
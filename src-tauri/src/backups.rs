@@ -0,0 +1,142 @@
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::paths;
+
+// Written into every backup zip next to the backed up files.
+const METADATA_FILENAME: &str = "rls_backup.json";
+
+// Metadata stored in a backup zip.
+pub trait BackupMetadata: Serialize + DeserializeOwned {
+    fn id(&self) -> &str;
+    fn created_at(&self) -> u64;
+    // Size of the zip on disk, filled in when reading.
+    fn set_size(&mut self, size: u64);
+    // The metadata is read back from a file on disk; refuse anything a
+    // restore must not act on.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// A folder of backup zips in the installer data folder, named
+// `<prefix>_<unix seconds>.zip`.
+pub struct BackupStore {
+    pub dirname: &'static str,
+    pub prefix: &'static str,
+    // For messages, e.g. "save backup".
+    pub noun: &'static str,
+}
+
+impl BackupStore {
+    pub fn dir(&self) -> Result<PathBuf, String> {
+        let dir = paths::data_dir()?.join(self.dirname);
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        Ok(dir)
+    }
+
+    // Zip of an existing backup; ids come from the UI.
+    pub fn path(&self, id: &str) -> Result<PathBuf, String> {
+        let path = self.dir()?.join(format!("{}.zip", id));
+        if id.contains(['/', '\\']) || !path.is_file() {
+            return Err(format!("No {} {}", self.noun, id));
+        }
+        Ok(path)
+    }
+
+    // First free id for a backup taken at `created_at`.
+    pub fn new_id(&self, created_at: u64) -> Result<String, String> {
+        let dir = self.dir()?;
+        Ok((0..)
+            .map(|n| if n == 0 { format!("{}_{}", self.prefix, created_at) } else { format!("{}_{}_{}", self.prefix, created_at, n) })
+            .find(|id| !dir.join(format!("{}.zip", id)).exists())
+            .unwrap_or_default())
+    }
+
+    // Write the backup with `fill`, which adds the backed up files and may
+    // complete the metadata, then append the metadata. Written under a
+    // temporary name so a failed backup never lists. Sets the zip's size.
+    pub fn write<T, F>(&self, metadata: &mut T, fill: F) -> Result<(), String>
+    where
+        T: BackupMetadata,
+        F: FnOnce(&mut ZipWriter<File>, &mut T) -> Result<(), String>,
+    {
+        let dir = self.dir()?;
+        let id = metadata.id().to_string();
+        let partial = dir.join(format!("{}.zip.partial", id));
+        let written = File::create(&partial).map_err(|e| e.to_string()).and_then(|file| {
+            let mut zip = ZipWriter::new(file);
+            fill(&mut zip, metadata)?;
+            let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+            zip.start_file(METADATA_FILENAME, options).map_err(|e| e.to_string())?;
+            let content = serde_json::to_vec_pretty(&*metadata).map_err(|e| e.to_string())?;
+            zip.write_all(&content).map_err(|e| e.to_string())?;
+            zip.finish().map_err(|e| e.to_string())?.sync_all().map_err(|e| e.to_string())
+        });
+        if let Err(e) = written {
+            let _ = fs::remove_file(&partial);
+            return Err(e);
+        }
+        let path = dir.join(format!("{}.zip", id));
+        fs::rename(&partial, &path).map_err(|e| e.to_string())?;
+        metadata.set_size(fs::metadata(&path).map(|m| m.len()).unwrap_or(0));
+        Ok(())
+    }
+
+    // Backups on disk, newest first. Zips that cannot be read are skipped.
+    pub fn list<T: BackupMetadata>(&self) -> Result<Vec<T>, String> {
+        let mut backups: Vec<T> = fs::read_dir(self.dir()?)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+            .filter_map(|path| match read_metadata(&path) {
+                Ok(backup) => Some(backup),
+                Err(e) => {
+                    eprintln!("Skipping unreadable {} {:?}: {}", self.noun, path, e);
+                    None
+                }
+            })
+            .collect();
+        backups.sort_by_key(|b| std::cmp::Reverse((b.created_at(), b.id().to_string())));
+        Ok(backups)
+    }
+
+    // Delete all but the newest `keep` of the backups `counts` selects;
+    // returns the removed ids.
+    pub fn prune<T, F>(&self, keep: usize, counts: F) -> Result<Vec<String>, String>
+    where
+        T: BackupMetadata,
+        F: Fn(&T) -> bool,
+    {
+        let mut removed = Vec::new();
+        for backup in self.list::<T>()?.into_iter().filter(|b| counts(b)).skip(keep) {
+            self.delete(backup.id())?;
+            removed.push(backup.id().to_string());
+        }
+        Ok(removed)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        fs::remove_file(self.path(id)?).map_err(|e| e.to_string())
+    }
+}
+
+pub fn read_metadata<T: BackupMetadata>(path: &Path) -> Result<T, String> {
+    let mut archive = ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    let mut content = String::new();
+    archive
+        .by_name(METADATA_FILENAME)
+        .map_err(|e| e.to_string())?
+        .read_to_string(&mut content)
+        .map_err(|e| e.to_string())?;
+    let mut backup: T = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    backup.validate()?;
+    backup.set_size(fs::metadata(path).map(|m| m.len()).unwrap_or(0));
+    Ok(backup)
+}
@@ -0,0 +1,254 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{fsutil, paths};
+
+const CACHE_DIRNAME: &str = "cache";
+const BLOBS_DIRNAME: &str = "blobs";
+const INDEX_FILENAME: &str = "index.json";
+
+// Least recently used downloads are evicted once the cache grows past this.
+const CACHE_MAX_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+// Downloads can run concurrently; serialize every read-modify-write of
+// the index so entries are not lost.
+static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheEntry {
+    sha256: String,
+    // Original file name of the download, restored on a cache hit.
+    filename: String,
+    size: u64,
+    added_at: u64,
+    last_used: u64,
+}
+
+// Last download of a URL and the validators the server sent with it, so
+// a cached copy is only reused after the server confirms it is current.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UrlSource {
+    pub sha256: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CacheIndex {
+    // sha256 -> entry
+    entries: BTreeMap<String, CacheEntry>,
+    // source url -> last download
+    #[serde(default)]
+    sources: BTreeMap<String, UrlSource>,
+}
+
+#[derive(Serialize)]
+pub struct CacheStats {
+    pub path: String,
+    pub entries: usize,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+}
+
+fn cache_dir() -> Result<PathBuf, String> {
+    let dir = paths::data_dir()?.join(CACHE_DIRNAME);
+    fs::create_dir_all(dir.join(BLOBS_DIRNAME)).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn blob_path(cache_dir: &Path, sha256: &str) -> PathBuf {
+    cache_dir.join(BLOBS_DIRNAME).join(sha256)
+}
+
+fn read_index(cache_dir: &Path) -> CacheIndex {
+    fs::read_to_string(cache_dir.join(INDEX_FILENAME))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_index(cache_dir: &Path, index: &CacheIndex) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(index).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&cache_dir.join(INDEX_FILENAME), content.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn remove_entry(cache_dir: &Path, index: &mut CacheIndex, sha256: &str) -> u64 {
+    index.sources.retain(|_, source| source.sha256 != sha256);
+    let Some(entry) = index.entries.remove(sha256) else {
+        return 0;
+    };
+    if let Err(e) = fs::remove_file(blob_path(cache_dir, sha256)) {
+        if e.kind() != io::ErrorKind::NotFound {
+            eprintln!("Failed to remove cached blob {}: {}", sha256, e);
+        }
+    }
+    entry.size
+}
+
+fn evict_to_fit(cache_dir: &Path, index: &mut CacheIndex) {
+    let mut total: u64 = index.entries.values().map(|e| e.size).sum();
+    while total > CACHE_MAX_BYTES {
+        let Some(oldest) = index
+            .entries
+            .values()
+            .min_by_key(|e| e.last_used)
+            .map(|e| e.sha256.clone())
+        else {
+            break;
+        };
+        total = total.saturating_sub(remove_entry(cache_dir, index, &oldest));
+    }
+}
+
+// Copy `from` to `to` while hashing, returning the lowercase hex SHA-256.
+fn copy_hashed(from: &Path, to: &Path) -> io::Result<String> {
+    let mut reader = fs::File::open(from)?;
+    let mut writer = fs::File::create(to)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+        writer.write_all(&buf[..read])?;
+    }
+    writer.sync_all()?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// What the cache knows about `url`, when the server sent a validator for
+// it. The caller revalidates with a conditional request before using it.
+pub fn source(url: &str) -> Option<UrlSource> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let index = read_index(&cache_dir().ok()?);
+    let source = index.sources.get(url)?;
+    let validated = source.etag.is_some() || source.last_modified.is_some();
+    (validated && index.entries.contains_key(&source.sha256)).then(|| source.clone())
+}
+
+// Copy the cached file with content hash `sha256` next to `requested_path`
+// under its original file name. Returns the path written, or None on a
+// cache miss. Corrupted blobs are dropped so the caller falls back to the
+// network.
+pub fn restore(url: &str, sha256: &str, requested_path: &Path) -> Option<PathBuf> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cache_dir = cache_dir().ok()?;
+    let mut index = read_index(&cache_dir);
+
+    let hash = sha256.to_lowercase();
+    let entry = index.entries.get(&hash)?.clone();
+
+    let dest_path = match requested_path.parent() {
+        Some(parent) if !entry.filename.is_empty() => parent.join(&entry.filename),
+        _ => requested_path.to_path_buf(),
+    };
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent).ok()?;
+    }
+
+    // Copied next to the destination first so a file already there is only
+    // replaced by a complete, verified copy.
+    let part_path = fsutil::part_path(&dest_path);
+    let copied = copy_hashed(&blob_path(&cache_dir, &hash), &part_path);
+    if copied.as_ref().is_ok_and(|actual| *actual == hash) {
+        // The cached copy is fine; keep it when the destination is in the way.
+        if let Err(e) = fsutil::move_file_with(&part_path, &dest_path, true) {
+            eprintln!("Failed to restore cached download {} to {:?}: {}", hash, dest_path, e);
+            let _ = fs::remove_file(&part_path);
+            return None;
+        }
+    }
+    match copied {
+        Ok(actual) if actual == hash => {
+            if let Some(entry) = index.entries.get_mut(&hash) {
+                entry.last_used = fsutil::unix_now();
+            }
+        }
+        result => {
+            eprintln!("Discarding cached download {} for {}: {:?}", hash, url, result.err());
+            let _ = fs::remove_file(&part_path);
+            remove_entry(&cache_dir, &mut index, &hash);
+            if let Err(e) = write_index(&cache_dir, &index) {
+                eprintln!("Failed to update download cache index: {}", e);
+            }
+            return None;
+        }
+    }
+
+    if let Err(e) = write_index(&cache_dir, &index) {
+        eprintln!("Failed to update download cache index: {}", e);
+    }
+    Some(dest_path)
+}
+
+// Add a finished download to the cache. `source.sha256` is the hash
+// computed while streaming the file to disk.
+pub fn store(path: &Path, url: &str, source: UrlSource) -> Result<(), String> {
+    let sha256 = source.sha256.as_str();
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cache_dir = cache_dir()?;
+    let mut index = read_index(&cache_dir);
+    let now = fsutil::unix_now();
+
+    let blob = blob_path(&cache_dir, sha256);
+    if !index.entries.contains_key(sha256) || !blob.exists() {
+        let tmp_blob = blob.with_extension("tmp");
+        let actual = copy_hashed(path, &tmp_blob).map_err(|e| e.to_string())?;
+        if actual != sha256 {
+            let _ = fs::remove_file(&tmp_blob);
+            return Err(format!("{} changed while being cached", path.display()));
+        }
+        fs::rename(&tmp_blob, &blob).map_err(|e| e.to_string())?;
+    }
+
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let size = fs::metadata(&blob).map(|m| m.len()).map_err(|e| e.to_string())?;
+    let entry = index.entries.entry(sha256.to_string()).or_insert(CacheEntry {
+        sha256: sha256.to_string(),
+        filename: filename.clone(),
+        size,
+        added_at: now,
+        last_used: now,
+    });
+    entry.filename = filename;
+    entry.last_used = now;
+    index.sources.insert(url.to_string(), source);
+
+    evict_to_fit(&cache_dir, &mut index);
+    write_index(&cache_dir, &index)
+}
+
+#[tauri::command]
+pub fn get_cache_stats() -> Result<CacheStats, String> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cache_dir = cache_dir()?;
+    let index = read_index(&cache_dir);
+    Ok(CacheStats {
+        path: cache_dir.to_string_lossy().to_string(),
+        entries: index.entries.len(),
+        total_bytes: index.entries.values().map(|e| e.size).sum(),
+        max_bytes: CACHE_MAX_BYTES,
+    })
+}
+
+// Remove every cached download. Returns the number of bytes freed.
+#[tauri::command]
+pub fn clear_cache() -> Result<u64, String> {
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let cache_dir = cache_dir()?;
+    let mut index = read_index(&cache_dir);
+    let hashes: Vec<String> = index.entries.keys().cloned().collect();
+    let freed = hashes.iter().map(|hash| remove_entry(&cache_dir, &mut index, hash)).sum();
+    write_index(&cache_dir, &index)?;
+    Ok(freed)
+}
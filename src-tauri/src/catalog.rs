@@ -0,0 +1,594 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog_sources::{self, CatalogSource, Location, OFFICIAL_SOURCE_ID};
+use crate::channels::Channel;
+use crate::version::VersionReq;
+use crate::{fsutil, paths};
+
+// One mod in the catalog (`mods.json`), in the catalog's camelCase layout.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModConfig {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_repo: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direct_download: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    // Builds on other release channels, e.g.
+    // `{ "beta": { "version": "2.7.0_beta1", "directDownload": "..." } }`.
+    // `version` and `directDownload` above are the stable build.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub channels: BTreeMap<Channel, Build>,
+    // Earlier releases that can still be installed, e.g. to stay on a
+    // version other mods depend on.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<Build>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+    // Case-insensitive regex matched against zip filenames in the mods folder.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    // Release notes for the mod, as a Markdown changelog with one heading
+    // per version or a JSON array of `{ version, date, notes }`. GitHub
+    // release notes are used when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changelog_url: Option<String>,
+    // Mods that must be installed alongside this one, optionally with a
+    // version constraint: `"rls_career_overhaul"` or
+    // `{ "id": "rls_career_overhaul", "version": ">=2.6" }`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<Dependency>,
+    // Mods that cannot be installed at the same time as this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    // Id of the catalog source the entry came from, set when loading.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<String>,
+}
+
+// One downloadable release of a mod.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Build {
+    pub version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub direct_download: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(from = "DependencySpec")]
+pub struct Dependency {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DependencySpec {
+    Id(String),
+    Full {
+        id: String,
+        #[serde(default)]
+        version: Option<String>,
+    },
+}
+
+impl From<DependencySpec> for Dependency {
+    fn from(spec: DependencySpec) -> Self {
+        match spec {
+            DependencySpec::Id(id) => Dependency { id, version: None },
+            DependencySpec::Full { id, version } => Dependency { id, version },
+        }
+    }
+}
+
+// Catalog shipped with the installer, the last resort when offline on a
+// first launch.
+const BUNDLED_CATALOG: &str = include_str!("../../public/mods.json");
+
+const CATALOG_DIRNAME: &str = "catalog";
+
+// Merged catalog from the last `load_catalog`, for commands that only get
+// a mod id from the UI.
+static LOADED_CATALOG: Mutex<Vec<ModConfig>> = Mutex::new(Vec::new());
+const CACHED_CATALOG_FILENAME: &str = "mods.json";
+const CATALOG_META_FILENAME: &str = "meta.json";
+
+// Where the entries of one catalog source came from this time.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadedFrom {
+    Remote,
+    Cached,
+    Bundled,
+    Local,
+}
+
+// Validators for the cached copy, sent back as conditional request headers.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+struct CatalogMeta {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    // Unix timestamp (seconds) of the last time the remote confirmed or
+    // replaced the cached copy.
+    fetched_at: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SourceStatus {
+    pub id: String,
+    pub name: String,
+    pub location: String,
+    // None when the source could not be loaded at all.
+    pub loaded_from: Option<LoadedFrom>,
+    pub entries: usize,
+    // When the catalog was last fetched from `location`; None for local
+    // and bundled catalogs.
+    pub fetched_at: Option<u64>,
+    pub age_secs: Option<u64>,
+    // Why the source could not be used as is, if it was not.
+    pub error: Option<String>,
+}
+
+// Every enabled source merged in priority order.
+#[derive(Serialize, Clone, Debug)]
+pub struct LoadedCatalog {
+    pub mods: Vec<ModConfig>,
+    // Entries left out or suspicious, so a typo in mods.json is visible.
+    pub issues: Vec<CatalogIssue>,
+    pub sources: Vec<SourceStatus>,
+}
+
+// Resolve the remote catalog URL with this priority:
+// 1) Runtime env REMOTE_MODS_JSON_URL (for local testing/overrides)
+// 2) Build-time REMOTE_MODS_JSON_URL via option_env! (for CI/Release builds)
+pub fn configured_url() -> Option<String> {
+    std::env::var("REMOTE_MODS_JSON_URL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(|| option_env!("REMOTE_MODS_JSON_URL").map(|s| s.to_string()).filter(|s| !s.is_empty()))
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueSeverity {
+    // The entry was left out of the catalog.
+    Error,
+    // The entry was kept but probably does not behave as intended.
+    Warning,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CatalogIssue {
+    // Catalog source the entry belongs to.
+    pub source: String,
+    // Position of the entry in the source's catalog array.
+    pub index: usize,
+    pub mod_id: Option<String>,
+    pub field: Option<String>,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ParsedCatalog {
+    pub mods: Vec<ModConfig>,
+    pub issues: Vec<CatalogIssue>,
+}
+
+const CATEGORIES: &[&str] = &["core", "map", "vehicle"];
+const STATES: &[&str] = &["Public", "Beta"];
+
+fn check_url(value: &str, allow_relative: bool) -> Result<(), String> {
+    if allow_relative && (value.starts_with("./") || value.starts_with('/')) {
+        return Ok(());
+    }
+    let url = reqwest::Url::parse(value).map_err(|e| format!("Invalid URL {:?}: {}", value, e))?;
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        scheme => Err(format!("Unsupported URL scheme {:?} in {:?}", scheme, value)),
+    }
+}
+
+// Problems with a single entry; any error drops the entry.
+fn check_entry(config: &ModConfig) -> Vec<(&'static str, IssueSeverity, String)> {
+    let mut issues = Vec::new();
+    let mut error = |field, message: String| issues.push((field, IssueSeverity::Error, message));
+
+    if config.id.trim().is_empty() {
+        error("id", "Missing mod id".to_string());
+    }
+    if config.name.trim().is_empty() {
+        error("name", "Missing mod name".to_string());
+    }
+    if let Some(pattern) = &config.asset_pattern {
+        if let Err(e) = regex::RegexBuilder::new(pattern).case_insensitive(true).build() {
+            error("assetPattern", format!("Invalid regex: {}", e));
+        }
+    }
+    if let Some(url) = &config.direct_download {
+        if let Err(e) = check_url(url, false) {
+            error("directDownload", e);
+        }
+    }
+    let builds = config.channels.values().map(|b| ("channels", b)).chain(config.versions.iter().map(|b| ("versions", b)));
+    for (field, build) in builds {
+        if build.version.trim().is_empty() {
+            error(field, "Missing build version".to_string());
+        }
+        if let Some(Err(e)) = build.direct_download.as_deref().map(|url| check_url(url, false)) {
+            error(field, e);
+        }
+    }
+    if let Some(repo) = &config.github_repo {
+        let mut parts = repo.split('/');
+        let valid = matches!((parts.next(), parts.next(), parts.next()), (Some(owner), Some(name), None) if !owner.is_empty() && !name.is_empty());
+        if !valid {
+            error("githubRepo", format!("Expected \"owner/repo\", got {:?}", repo));
+        }
+    }
+
+    for dependency in &config.dependencies {
+        if dependency.id.trim().is_empty() || dependency.id == config.id {
+            error("dependencies", format!("Invalid dependency {:?}", dependency.id));
+        }
+        if let Some(Err(e)) = dependency.version.as_deref().map(VersionReq::parse) {
+            error("dependencies", format!("{} for {}", e, dependency.id));
+        }
+    }
+    if config.conflicts.contains(&config.id) {
+        error("conflicts", "A mod cannot conflict with itself".to_string());
+    }
+
+    if let Some(url) = &config.changelog_url {
+        if let Err(e) = check_url(url, false) {
+            issues.push(("changelogUrl", IssueSeverity::Warning, e));
+        }
+    }
+    if let Some(url) = &config.image_url {
+        if let Err(e) = check_url(url, true) {
+            issues.push(("imageUrl", IssueSeverity::Warning, e));
+        }
+    }
+    if let Some(category) = config.category.as_deref().filter(|c| !CATEGORIES.contains(c)) {
+        issues.push(("category", IssueSeverity::Warning, format!("Unknown category {:?}", category)));
+    }
+    if let Some(state) = config.state.as_deref().filter(|s| !STATES.contains(s)) {
+        issues.push(("state", IssueSeverity::Warning, format!("Unknown state {:?}", state)));
+    }
+    issues
+}
+
+// File name a mod downloads as, used to probe the other mods' patterns.
+fn download_filename(config: &ModConfig) -> Option<String> {
+    let url = reqwest::Url::parse(config.direct_download.as_deref()?).ok()?;
+    url.path_segments()?.next_back().filter(|name| !name.is_empty()).map(str::to_string)
+}
+
+// Parse and validate a catalog document. Only a document that is not a
+// JSON array fails as a whole; bad entries are reported and left out.
+pub fn parse(content: &str) -> Result<ParsedCatalog, String> {
+    let entries: Vec<serde_json::Value> = serde_json::from_str(content).map_err(|e| format!("Invalid catalog: {}", e))?;
+    let mut parsed = ParsedCatalog::default();
+    let mut indices = Vec::new();
+
+    for (index, value) in entries.iter().enumerate() {
+        let issue = |mod_id: Option<String>, field: Option<&str>, severity, message| CatalogIssue {
+            source: String::new(),
+            index,
+            mod_id,
+            field: field.map(str::to_string),
+            severity,
+            message,
+        };
+        let config = match serde_json::from_value::<ModConfig>(value.clone()) {
+            Ok(config) => config,
+            Err(e) => {
+                let mod_id = value.get("id").and_then(|v| v.as_str()).map(str::to_string);
+                parsed.issues.push(issue(mod_id, None, IssueSeverity::Error, e.to_string()));
+                continue;
+            }
+        };
+
+        let mut rejected = false;
+        for (field, severity, message) in check_entry(&config) {
+            rejected |= severity == IssueSeverity::Error;
+            parsed.issues.push(issue(Some(config.id.clone()), Some(field), severity, message));
+        }
+        if let Some(first) = parsed.mods.iter().position(|m| m.id == config.id) {
+            rejected = true;
+            let message = format!("Duplicate id, already used by entry {}", indices[first]);
+            parsed.issues.push(issue(Some(config.id.clone()), Some("id"), IssueSeverity::Error, message));
+        }
+        if !rejected {
+            parsed.mods.push(config);
+            indices.push(index);
+        }
+    }
+
+    // A pattern that also matches another mod's download would make the
+    // installer treat that mod's zip as its own.
+    for (position, config) in parsed.mods.iter().enumerate() {
+        let Some(pattern) = config.asset_pattern.as_deref() else {
+            continue;
+        };
+        let Ok(pattern) = regex::RegexBuilder::new(pattern).case_insensitive(true).build() else {
+            continue;
+        };
+        for other in parsed.mods.iter().filter(|other| other.id != config.id) {
+            if let Some(filename) = download_filename(other).filter(|f| pattern.is_match(f)) {
+                parsed.issues.push(CatalogIssue {
+                    source: String::new(),
+                    index: indices[position],
+                    mod_id: Some(config.id.clone()),
+                    field: Some("assetPattern".to_string()),
+                    severity: IssueSeverity::Warning,
+                    message: format!("Pattern also matches {} ({})", filename, other.id),
+                });
+            }
+        }
+    }
+
+    if parsed.mods.is_empty() && !entries.is_empty() {
+        return Err(format!("Catalog has no valid entries ({} rejected)", entries.len()));
+    }
+    Ok(parsed)
+}
+
+// Cached copy of each remote source lives in `catalog/<source id>/`.
+fn catalog_dir(source_id: &str) -> Result<PathBuf, String> {
+    let dir = paths::data_dir()?.join(CATALOG_DIRNAME).join(source_id);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn read_meta(dir: &Path) -> Option<CatalogMeta> {
+    let content = fs::read_to_string(dir.join(CATALOG_META_FILENAME)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn write_meta(dir: &Path, meta: &CatalogMeta) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(meta).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&dir.join(CATALOG_META_FILENAME), content.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn read_cached(dir: &Path) -> Option<(ParsedCatalog, CatalogMeta)> {
+    let meta = read_meta(dir)?;
+    let content = fs::read_to_string(dir.join(CACHED_CATALOG_FILENAME)).ok()?;
+    match parse(&content) {
+        Ok(catalog) => Some((catalog, meta)),
+        Err(e) => {
+            eprintln!("Ignoring damaged cached catalog: {}", e);
+            None
+        }
+    }
+}
+
+fn header(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+}
+
+// Fetch the remote catalog, revalidating the cached copy when it came from
+// the same URL. Only a catalog that parses replaces the cached one.
+async fn fetch_remote(dir: &Path, url: &str) -> Result<(ParsedCatalog, CatalogMeta), String> {
+    let cached = read_cached(dir).filter(|(_, meta)| meta.url == url);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.get(url);
+    if let Some((_, meta)) = &cached {
+        if let Some(etag) = &meta.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &meta.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let response = request.send().await.map_err(|e| e.to_string())?;
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        let (catalog, mut meta) = cached.ok_or("Catalog server answered 304 without a cached copy")?;
+        meta.fetched_at = fsutil::unix_now();
+        write_meta(dir, &meta)?;
+        return Ok((catalog, meta));
+    }
+    if !response.status().is_success() {
+        return Err(format!("Catalog request failed with status: {}", response.status()));
+    }
+
+    let etag = header(&response, reqwest::header::ETAG);
+    let last_modified = header(&response, reqwest::header::LAST_MODIFIED);
+    let content = response.text().await.map_err(|e| e.to_string())?;
+    let catalog = parse(&content)?;
+
+    fsutil::write_atomic(&dir.join(CACHED_CATALOG_FILENAME), content.as_bytes()).map_err(|e| e.to_string())?;
+    let meta = CatalogMeta {
+        url: url.to_string(),
+        etag,
+        last_modified,
+        fetched_at: fsutil::unix_now(),
+    };
+    write_meta(dir, &meta)?;
+    Ok((catalog, meta))
+}
+
+fn status(source: &CatalogSource, location: String) -> SourceStatus {
+    SourceStatus {
+        id: source.id.clone(),
+        name: source.name.clone(),
+        location,
+        loaded_from: None,
+        entries: 0,
+        fetched_at: None,
+        age_secs: None,
+        error: None,
+    }
+}
+
+fn with_meta(mut status: SourceStatus, meta: &CatalogMeta) -> SourceStatus {
+    status.fetched_at = Some(meta.fetched_at);
+    status.age_secs = Some(fsutil::unix_now().saturating_sub(meta.fetched_at));
+    status
+}
+
+// Load one source: a remote one when reachable, otherwise the last good
+// copy fetched, otherwise (official source only) the bundled catalog.
+async fn load_source(source: &CatalogSource) -> (Option<ParsedCatalog>, SourceStatus) {
+    let location = catalog_sources::resolve_location(source);
+    let mut status = status(source, location.to_string());
+
+    let url = match location {
+        Location::Local(path) => {
+            match fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|content| parse(&content)) {
+                Ok(catalog) => {
+                    status.loaded_from = Some(LoadedFrom::Local);
+                    return (Some(catalog), status);
+                }
+                Err(e) => {
+                    status.error = Some(e);
+                    return (None, status);
+                }
+            }
+        }
+        Location::Remote(url) => url,
+        Location::Bundled => {
+            match parse(BUNDLED_CATALOG) {
+                Ok(catalog) => {
+                    status.loaded_from = Some(LoadedFrom::Bundled);
+                    return (Some(catalog), status);
+                }
+                Err(e) => {
+                    status.error = Some(format!("Bundled catalog is invalid: {}", e));
+                    return (None, status);
+                }
+            }
+        }
+    };
+
+    let dir = match catalog_dir(&source.id) {
+        Ok(dir) => dir,
+        Err(e) => {
+            status.error = Some(e);
+            return (None, status);
+        }
+    };
+    let error = match fetch_remote(&dir, &url).await {
+        Ok((catalog, meta)) => {
+            status.loaded_from = Some(LoadedFrom::Remote);
+            return (Some(catalog), with_meta(status, &meta));
+        }
+        Err(e) => {
+            eprintln!("Failed to load remote catalog {}: {}", url, e);
+            e
+        }
+    };
+    status.error = Some(error);
+
+    if let Some((catalog, meta)) = read_cached(&dir) {
+        status.loaded_from = Some(LoadedFrom::Cached);
+        return (Some(catalog), with_meta(status, &meta));
+    }
+    if source.id == OFFICIAL_SOURCE_ID {
+        match parse(BUNDLED_CATALOG) {
+            Ok(catalog) => {
+                status.loaded_from = Some(LoadedFrom::Bundled);
+                return (Some(catalog), status);
+            }
+            Err(e) => eprintln!("Bundled catalog is invalid: {}", e),
+        }
+    }
+    (None, status)
+}
+
+// Entries of the official source keep their plain ids so existing
+// manifests still match; every other source is namespaced as `source:id`.
+fn namespaced(source_id: &str, id: &str) -> String {
+    if source_id == OFFICIAL_SOURCE_ID {
+        id.to_string()
+    } else {
+        format!("{}:{}", source_id, id)
+    }
+}
+
+// Dependency and conflict ids are relative to their own source unless
+// they name another one as `source:id`.
+fn referenced(source_id: &str, id: &str) -> String {
+    match id.split_once(':') {
+        Some((OFFICIAL_SOURCE_ID, rest)) => rest.to_string(),
+        Some(_) => id.to_string(),
+        None => namespaced(source_id, id),
+    }
+}
+
+// Load every enabled catalog source and merge them in priority order,
+// tagging each entry with the source it came from.
+#[tauri::command]
+pub async fn load_catalog() -> Result<LoadedCatalog, String> {
+    let mut merged = LoadedCatalog {
+        mods: Vec::new(),
+        issues: Vec::new(),
+        sources: Vec::new(),
+    };
+
+    for source in catalog_sources::effective_sources().into_iter().filter(|s| s.enabled) {
+        let (catalog, mut status) = load_source(&source).await;
+        if let Some(catalog) = catalog {
+            status.entries = catalog.mods.len();
+            for mut config in catalog.mods {
+                config.id = namespaced(&source.id, &config.id);
+                for dependency in config.dependencies.iter_mut() {
+                    dependency.id = referenced(&source.id, &dependency.id);
+                }
+                for conflict in config.conflicts.iter_mut() {
+                    *conflict = referenced(&source.id, conflict);
+                }
+                config.origin = Some(source.id.clone());
+                merged.mods.push(config);
+            }
+            for mut issue in catalog.issues {
+                issue.source = source.id.clone();
+                issue.mod_id = issue.mod_id.map(|id| namespaced(&source.id, &id));
+                merged.issues.push(issue);
+            }
+        }
+        merged.sources.push(status);
+    }
+
+    if merged.sources.iter().all(|s| s.loaded_from.is_none()) && !merged.sources.is_empty() {
+        return Err("No catalog source could be loaded".to_string());
+    }
+    *LOADED_CATALOG.lock().unwrap_or_else(|e| e.into_inner()) = merged.mods.clone();
+    Ok(merged)
+}
+
+// Merged catalog from the last `load_catalog`, empty before the first.
+pub fn loaded() -> Vec<ModConfig> {
+    LOADED_CATALOG.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// Entry of the last loaded catalog with this id.
+pub fn find_loaded(mod_id: &str) -> Option<ModConfig> {
+    LOADED_CATALOG
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|config| config.id == mod_id)
+        .cloned()
+}
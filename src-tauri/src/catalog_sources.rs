@@ -0,0 +1,178 @@
+use std::fmt;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::catalog;
+use crate::settings;
+
+// The RLS catalog. Its entries are not namespaced and it is the only
+// source with a bundled fallback.
+pub const OFFICIAL_SOURCE_ID: &str = "official";
+
+// Priority given to sources added without one, after the official catalog.
+const DEFAULT_PRIORITY: i32 = 10;
+
+fn default_priority() -> i32 {
+    DEFAULT_PRIORITY
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+// A catalog to load mods from, stored in settings.json.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct CatalogSource {
+    // Lowercase letters, digits, `-` and `_`; also the namespace of the
+    // source's mod ids.
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    // http(s) URL, or absolute path of a local mods.json. Empty for the
+    // official source means REMOTE_MODS_JSON_URL, or the bundled catalog
+    // when that is not set.
+    #[serde(default)]
+    pub location: String,
+    // Lower loads and is listed first.
+    #[serde(default = "default_priority")]
+    pub priority: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+pub enum Location {
+    Remote(String),
+    Local(PathBuf),
+    // The catalog shipped with the installer.
+    Bundled,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Remote(url) => f.write_str(url),
+            Location::Local(path) => write!(f, "{}", path.display()),
+            Location::Bundled => f.write_str("bundled catalog"),
+        }
+    }
+}
+
+fn is_remote(location: &str) -> bool {
+    location.starts_with("https://") || location.starts_with("http://")
+}
+
+pub fn resolve_location(source: &CatalogSource) -> Location {
+    if source.id == OFFICIAL_SOURCE_ID && source.location.is_empty() {
+        return catalog::configured_url().map_or(Location::Bundled, Location::Remote);
+    }
+    if is_remote(&source.location) {
+        Location::Remote(source.location.clone())
+    } else {
+        Location::Local(PathBuf::from(&source.location))
+    }
+}
+
+fn official_source() -> CatalogSource {
+    CatalogSource {
+        id: OFFICIAL_SOURCE_ID.to_string(),
+        name: "RLS".to_string(),
+        location: String::new(),
+        priority: 0,
+        enabled: true,
+    }
+}
+
+// Configured sources plus the official one unless settings override it,
+// sorted by priority then id.
+pub fn effective_sources() -> Vec<CatalogSource> {
+    let mut sources = settings::load().catalog_sources;
+    if !sources.iter().any(|s| s.id == OFFICIAL_SOURCE_ID) {
+        sources.push(official_source());
+    }
+    sources.sort_by(|a, b| (a.priority, &a.id).cmp(&(b.priority, &b.id)));
+    sources
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+// Check a new list of sources. Local files can only be added through the
+// file dialog, so a location that is not a URL must already be configured.
+fn validate(sources: &[CatalogSource], current: &[CatalogSource]) -> Result<(), String> {
+    for (index, source) in sources.iter().enumerate() {
+        if !valid_id(&source.id) {
+            return Err(format!("Invalid catalog source id {:?}: use lowercase letters, digits, - and _", source.id));
+        }
+        if sources[..index].iter().any(|other| other.id == source.id) {
+            return Err(format!("Duplicate catalog source id {:?}", source.id));
+        }
+        if source.id == OFFICIAL_SOURCE_ID && source.location.is_empty() {
+            continue;
+        }
+        if is_remote(&source.location) {
+            reqwest::Url::parse(&source.location).map_err(|e| format!("Invalid catalog URL {:?}: {}", source.location, e))?;
+        } else if !current.iter().any(|other| other.location == source.location) {
+            return Err(format!("{:?} is not an http(s) URL or a catalog file added from this computer", source.location));
+        }
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_catalog_sources() -> Vec<CatalogSource> {
+    effective_sources()
+}
+
+// Replace the configured sources (order, priority, enabled flags, URLs).
+#[tauri::command]
+pub fn set_catalog_sources(sources: Vec<CatalogSource>) -> Result<Vec<CatalogSource>, String> {
+    validate(&sources, &effective_sources())?;
+    settings::update(|settings| settings.catalog_sources = sources)?;
+    Ok(effective_sources())
+}
+
+// Let the user pick a local mods.json in a native dialog and add it as a
+// source after the existing ones. Returns None when cancelled.
+#[tauri::command]
+pub async fn add_local_catalog_source<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> Result<Option<CatalogSource>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Select a mod catalog")
+        .add_filter("Mod catalog", &["json"])
+        .pick_file(move |file| {
+            let _ = tx.send(file);
+        });
+
+    let Some(file) = rx.await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let path = file.into_path().map_err(|e| e.to_string())?;
+
+    let mut sources = effective_sources();
+    let stem: String = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '_' })
+        .collect();
+    let base = if stem.is_empty() || stem == OFFICIAL_SOURCE_ID { "local".to_string() } else { stem };
+    let id = (1..)
+        .map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+        .find(|candidate| !sources.iter().any(|s| s.id == *candidate))
+        .unwrap_or(base);
+
+    let source = CatalogSource {
+        id,
+        name: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        location: path.to_string_lossy().to_string(),
+        priority: sources.iter().map(|s| s.priority).max().unwrap_or(0) + DEFAULT_PRIORITY,
+        enabled: true,
+    };
+    sources.push(source.clone());
+    settings::update(|settings| settings.catalog_sources = sources)?;
+    Ok(Some(source))
+}
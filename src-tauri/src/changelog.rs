@@ -0,0 +1,214 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::{self, ModConfig};
+use crate::github::GithubApi;
+use crate::version::Version;
+use crate::{fsutil, paths};
+
+const CHANGELOG_CACHE_FILENAME: &str = "changelog_cache.json";
+
+// Serializes read-modify-write cycles of the changelog cache.
+static CHANGELOG_CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+// Markdown heading that starts a version section, e.g. `## 2.6.2`,
+// `## [2.6.2] - 2025-01-12` or `### v2.6.2_hotfix`.
+static VERSION_HEADING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^#{1,4}\s*\[?[vV]?(\d+(?:[._-]?[0-9A-Za-z]+)*)\]?(?:\s*[-–(]\s*([^)]*)\)?)?").unwrap());
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChangelogEntry {
+    pub version: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default, alias = "date")]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangelogSource {
+    ChangelogUrl,
+    Github,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Changelog {
+    pub mod_id: String,
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub source: ChangelogSource,
+    // Newest first.
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CachedChangelog {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content: String,
+    fetched_at: u64,
+}
+
+fn cache_path() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(CHANGELOG_CACHE_FILENAME))
+}
+
+fn read_cache() -> BTreeMap<String, CachedChangelog> {
+    cache_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn store(url: &str, cached: CachedChangelog) -> Result<(), String> {
+    let _guard = CHANGELOG_CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut cache = read_cache();
+    cache.insert(url.to_string(), cached);
+    let content = serde_json::to_string(&cache).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&cache_path()?, content.as_bytes()).map_err(|e| e.to_string())
+}
+
+// Fetch a changelog document, revalidating the cached copy and falling
+// back to it when the server cannot be reached.
+async fn fetch_cached(url: &str) -> Result<String, String> {
+    let cached = read_cache().remove(url);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let mut request = client.get(url);
+    if let Some(cached) = &cached {
+        if let Some(etag) = &cached.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = match request.send().await {
+        Ok(response) if response.status() == reqwest::StatusCode::NOT_MODIFIED => {
+            return cached.map(|c| c.content).ok_or_else(|| "Changelog server answered 304 without a cached copy".to_string());
+        }
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            let error = format!("Changelog request failed with status: {}", response.status());
+            return cached.map(|c| c.content).ok_or(error);
+        }
+        Err(e) => return cached.map(|c| c.content).ok_or_else(|| e.to_string()),
+    };
+
+    let header = |name: reqwest::header::HeaderName| {
+        response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let content = response.text().await.map_err(|e| e.to_string())?;
+
+    let fresh = CachedChangelog {
+        etag,
+        last_modified,
+        content: content.clone(),
+        fetched_at: fsutil::unix_now(),
+    };
+    if let Err(e) = store(url, fresh) {
+        eprintln!("Failed to cache changelog {}: {}", url, e);
+    }
+    Ok(content)
+}
+
+// Split a Markdown changelog into one entry per version heading. Text
+// before the first version heading is ignored.
+pub fn parse_markdown(content: &str) -> Vec<ChangelogEntry> {
+    let mut entries: Vec<ChangelogEntry> = Vec::new();
+    for line in content.lines() {
+        if let Some(caps) = VERSION_HEADING.captures(line) {
+            entries.push(ChangelogEntry {
+                version: caps[1].to_string(),
+                title: Some(line.trim_start_matches('#').trim().to_string()),
+                published_at: caps.get(2).map(|m| m.as_str().trim().to_string()).filter(|d| !d.is_empty()),
+                notes: String::new(),
+                url: None,
+            });
+        } else if let Some(entry) = entries.last_mut() {
+            entry.notes.push_str(line);
+            entry.notes.push('\n');
+        }
+    }
+    for entry in entries.iter_mut() {
+        entry.notes = entry.notes.trim().to_string();
+    }
+    entries
+}
+
+// A changelog document is either a JSON array of entries or Markdown.
+pub fn parse_document(content: &str) -> Vec<ChangelogEntry> {
+    serde_json::from_str(content).unwrap_or_else(|_| parse_markdown(content))
+}
+
+// Entries newer than `from` (exclusive) and up to `to` (inclusive), newest
+// first.
+pub fn select_range(entries: Vec<ChangelogEntry>, from: Option<&str>, to: Option<&str>) -> Vec<ChangelogEntry> {
+    let from = from.map(Version::parse).filter(|v| !v.is_unknown());
+    let to = to.map(Version::parse).filter(|v| !v.is_unknown());
+    let mut selected: Vec<(Version, ChangelogEntry)> = entries
+        .into_iter()
+        .map(|entry| (Version::parse(&entry.version), entry))
+        .filter(|(version, _)| {
+            from.as_ref().is_none_or(|from| version > from) && to.as_ref().is_none_or(|to| version <= to)
+        })
+        .collect();
+    selected.sort_by(|a, b| b.0.cmp(&a.0));
+    selected.into_iter().map(|(_, entry)| entry).collect()
+}
+
+async fn github_entries(config: &ModConfig) -> Result<Vec<ChangelogEntry>, String> {
+    let repo = config.github_repo.as_deref().unwrap_or_default();
+    let releases = GithubApi::from_settings()?.releases(repo).await?;
+    Ok(releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .map(|release| ChangelogEntry {
+            version: crate::github::version_from_tag(&release.tag_name),
+            title: release.name.filter(|n| !n.is_empty()),
+            published_at: release.published_at,
+            notes: release.body.unwrap_or_default().trim().to_string(),
+            url: release.html_url,
+        })
+        .collect())
+}
+
+// Release notes for every version of a mod after `from_version` up to
+// `to_version` (the newest when unset), from the catalog's `changelogUrl`
+// or the mod's GitHub releases.
+#[tauri::command]
+pub async fn get_changelog(mod_id: String, from_version: Option<String>, to_version: Option<String>) -> Result<Changelog, String> {
+    let config = catalog::find_loaded(&mod_id).ok_or_else(|| format!("{} is not in the loaded catalog", mod_id))?;
+
+    let (source, entries) = if let Some(url) = config.changelog_url.as_deref() {
+        (ChangelogSource::ChangelogUrl, parse_document(&fetch_cached(url).await?))
+    } else if config.github_repo.is_some() {
+        (ChangelogSource::Github, github_entries(&config).await?)
+    } else {
+        return Err(format!("No changelog is published for {}", config.name));
+    };
+
+    Ok(Changelog {
+        entries: select_range(entries, from_version.as_deref(), to_version.as_deref()),
+        mod_id,
+        from_version,
+        to_version,
+        source,
+    })
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+use crate::catalog::{Build, ModConfig};
+use crate::settings;
+use crate::updates::AvailableVersion;
+use crate::version::Version;
+
+// Release channel a user follows for a mod. Stable is what the catalog
+// entry itself describes; beta builds are listed under `channels`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    #[default]
+    Stable,
+    Beta,
+}
+
+pub fn preferred(mod_id: &str) -> Channel {
+    settings::load().mod_channels.get(mod_id).copied().unwrap_or_default()
+}
+
+fn stable_build(config: &ModConfig) -> Option<Build> {
+    // The entry's own `directDownload` is the stable link.
+    let listed = config.channels.get(&Channel::Stable).cloned().map(|build| Build {
+        direct_download: build.direct_download.or_else(|| config.direct_download.clone()),
+        ..build
+    });
+    listed.or_else(|| {
+        Some(Build {
+            version: config.version.clone()?,
+            direct_download: config.direct_download.clone(),
+        })
+    })
+}
+
+// Build offered to a user on `channel`. Beta users get the beta build only
+// while it is newer than the stable one, so a stable release that
+// supersedes a beta is not held back.
+pub fn build_for(config: &ModConfig, channel: Channel) -> Option<Build> {
+    let stable = stable_build(config);
+    if channel == Channel::Stable {
+        return stable;
+    }
+    match (config.channels.get(&Channel::Beta), stable) {
+        (Some(beta), Some(stable)) if Version::parse(&stable.version) >= Version::parse(&beta.version) => Some(stable),
+        (Some(beta), _) => Some(beta.clone()),
+        (None, stable) => stable,
+    }
+}
+
+// Catalog entries with `version` and `directDownload` replaced by the build
+// of each user's preferred channel. Only apply to the catalog as loaded.
+pub fn apply_preferences(mut catalog: Vec<ModConfig>) -> Vec<ModConfig> {
+    let preferences = settings::load().mod_channels;
+    for config in catalog.iter_mut() {
+        let channel = preferences.get(&config.id).copied().unwrap_or_default();
+        if let Some(build) = build_for(config, channel) {
+            config.version = Some(build.version);
+            // A build without a link must not inherit the stable one, or
+            // the stable zip would be installed under the beta version.
+            // GitHub resolution, or an error, follows instead.
+            config.direct_download = build.direct_download;
+        }
+    }
+    catalog
+}
+
+// Latest version of every catalog entry without a GitHub repo on the
+// user's channel, ready for `check_updates`.
+#[tauri::command]
+pub fn channel_versions(catalog: Vec<ModConfig>) -> Vec<AvailableVersion> {
+    apply_preferences(catalog)
+        .into_iter()
+        .filter(|config| config.github_repo.is_none())
+        .filter_map(|config| {
+            Some(AvailableVersion {
+                version: config.version?,
+                download_url: config.direct_download,
+                mod_id: config.id,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_mod_channels() -> BTreeMap<String, Channel> {
+    settings::load().mod_channels
+}
+
+// Follow `channel` for a mod; stable is the default and is not stored.
+#[tauri::command]
+pub fn set_mod_channel(mod_id: String, channel: Channel) -> Result<BTreeMap<String, Channel>, String> {
+    let settings = settings::update(|settings| match channel {
+        Channel::Stable => {
+            settings.mod_channels.remove(&mod_id);
+        }
+        channel => {
+            settings.mod_channels.insert(mod_id, channel);
+        }
+    })?;
+    Ok(settings.mod_channels)
+}
@@ -0,0 +1,391 @@
+use std::collections::{BTreeSet, HashMap};
+use serde::Serialize;
+
+use crate::catalog::ModConfig;
+use crate::manifest::Manifest;
+use crate::version::{Version, VersionReq};
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StepReason {
+    Requested,
+    Dependency { of: String, constraint: Option<String> },
+}
+
+// One mod to download and install, in the order of the plan.
+#[derive(Serialize, Clone, Debug)]
+pub struct PlanStep {
+    pub mod_id: String,
+    pub name: String,
+    pub version: Option<String>,
+    pub download_url: Option<String>,
+    pub installed_version: Option<String>,
+    pub reason: StepReason,
+}
+
+// A dependency that is already installed in a suitable version.
+#[derive(Serialize, Clone, Debug)]
+pub struct SatisfiedDependency {
+    pub mod_id: String,
+    pub installed_version: String,
+    pub required_by: String,
+    pub constraint: Option<String>,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct InstallPlan {
+    // Dependencies come before the mods that need them.
+    pub steps: Vec<PlanStep>,
+    pub satisfied: Vec<SatisfiedDependency>,
+}
+
+// The mod the user asked for, with the version and URL already resolved
+// by the caller when they differ from the catalog entry.
+pub struct Target {
+    pub mod_id: String,
+    pub version: Option<String>,
+    pub download_url: Option<String>,
+}
+
+struct Resolver<'a> {
+    catalog: HashMap<&'a str, &'a ModConfig>,
+    installed: &'a Manifest,
+    target: &'a Target,
+    // Mods being visited, to report dependency cycles.
+    stack: Vec<String>,
+    planned: BTreeSet<String>,
+    plan: InstallPlan,
+}
+
+impl<'a> Resolver<'a> {
+    fn lookup(&self, id: &str) -> Result<&'a ModConfig, String> {
+        self.catalog
+            .get(id)
+            .copied()
+            .ok_or_else(|| match self.stack.last() {
+                Some(parent) => format!("{} requires {}, which is not in any catalog", parent, id),
+                None => format!("{} is not in any catalog", id),
+            })
+    }
+
+    fn planned_version(&self, config: &ModConfig) -> Option<String> {
+        if config.id == self.target.mod_id && self.target.version.is_some() {
+            self.target.version.clone()
+        } else {
+            config.version.clone()
+        }
+    }
+
+    fn visit(&mut self, id: &str, reason: StepReason) -> Result<(), String> {
+        if let Some(start) = self.stack.iter().position(|s| s == id) {
+            let mut cycle = self.stack[start..].to_vec();
+            cycle.push(id.to_string());
+            return Err(format!("Dependency cycle: {}", cycle.join(" -> ")));
+        }
+        if self.planned.contains(id) {
+            return Ok(());
+        }
+        let config = self.lookup(id)?;
+        self.stack.push(id.to_string());
+
+        for dependency in &config.dependencies {
+            let dep_config = self.lookup(&dependency.id)?;
+            let requirement = dependency.version.as_deref().map(VersionReq::parse).transpose()?;
+            let satisfies = |version: &str| requirement.as_ref().is_none_or(|req| req.matches(&Version::parse(version)));
+
+            if self.planned.contains(&dependency.id) {
+                if let Some(planned) = self.plan.steps.iter().find(|s| s.mod_id == dependency.id) {
+                    if planned.version.as_deref().is_some_and(|v| !satisfies(v)) {
+                        return Err(format!(
+                            "{} requires {} {}, but {} is being installed",
+                            id,
+                            dependency.id,
+                            dependency.version.as_deref().unwrap_or_default(),
+                            planned.version.as_deref().unwrap_or_default()
+                        ));
+                    }
+                }
+                continue;
+            }
+            if let Some(entry) = self.installed.mods.get(&dependency.id) {
+                if satisfies(&entry.version) {
+                    self.plan.satisfied.push(SatisfiedDependency {
+                        mod_id: dependency.id.clone(),
+                        installed_version: entry.version.clone(),
+                        required_by: id.to_string(),
+                        constraint: dependency.version.clone(),
+                    });
+                    continue;
+                }
+                if entry.pinned {
+                    return Err(format!(
+                        "{} requires {} {}, but {} is pinned to {}",
+                        id,
+                        dependency.id,
+                        dependency.version.as_deref().unwrap_or_default(),
+                        dependency.id,
+                        entry.version
+                    ));
+                }
+            }
+            if let Some(available) = self.planned_version(dep_config).filter(|v| !satisfies(v)) {
+                return Err(format!(
+                    "{} requires {} {}, but only {} is available",
+                    id,
+                    dependency.id,
+                    dependency.version.as_deref().unwrap_or_default(),
+                    available
+                ));
+            }
+
+            self.visit(
+                &dependency.id,
+                StepReason::Dependency {
+                    of: id.to_string(),
+                    constraint: dependency.version.clone(),
+                },
+            )?;
+        }
+
+        self.stack.pop();
+        let download_url = if config.id == self.target.mod_id && self.target.download_url.is_some() {
+            self.target.download_url.clone()
+        } else {
+            config.direct_download.clone()
+        };
+        self.plan.steps.push(PlanStep {
+            mod_id: config.id.clone(),
+            name: config.name.clone(),
+            version: self.planned_version(config),
+            download_url,
+            installed_version: self.installed.mods.get(&config.id).map(|e| e.version.clone()),
+            reason,
+        });
+        self.planned.insert(config.id.clone());
+        Ok(())
+    }
+
+    // Refuse plans that would leave two conflicting mods installed, in
+    // either direction of the declaration.
+    fn check_conflicts(&self) -> Result<(), String> {
+        let describe = |id: &str| if self.planned.contains(id) { "being installed" } else { "installed" };
+        let installed = self.installed.mods.keys().map(String::as_str);
+        let all: BTreeSet<&str> = installed.chain(self.planned.iter().map(String::as_str)).collect();
+
+        for id in &all {
+            let Some(config) = self.catalog.get(id) else {
+                continue;
+            };
+            for other in &config.conflicts {
+                let touches_plan = self.planned.contains(*id) || self.planned.contains(other);
+                if other != id && all.contains(other.as_str()) && touches_plan {
+                    return Err(format!(
+                        "{} ({}) conflicts with {} ({})",
+                        id,
+                        describe(id),
+                        other,
+                        describe(other)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Work out everything that has to be installed for `target`: missing or
+// too old dependencies first, then the mod itself. Fails on unknown
+// dependencies, unsatisfiable constraints, pinned dependencies that would
+// have to change, cycles and known conflicts.
+pub fn resolve(target: &Target, catalog: &[ModConfig], installed: &Manifest) -> Result<InstallPlan, String> {
+    let mut resolver = Resolver {
+        catalog: catalog.iter().map(|config| (config.id.as_str(), config)).collect(),
+        installed,
+        target,
+        stack: Vec::new(),
+        planned: BTreeSet::new(),
+        plan: InstallPlan::default(),
+    };
+    resolver.visit(&target.mod_id, StepReason::Requested)?;
+    resolver.check_conflicts()?;
+    Ok(resolver.plan)
+}
+
+// Check every planned version against the requirements of the planned
+// mods that depend on it. Versions that were unknown when the plan was
+// made, like GitHub releases resolved at install time, are only checked
+// here.
+pub fn check_planned_versions(plan: &InstallPlan, catalog: &[ModConfig]) -> Result<(), String> {
+    for step in &plan.steps {
+        let Some(config) = catalog.iter().find(|c| c.id == step.mod_id) else {
+            continue;
+        };
+        for dependency in &config.dependencies {
+            let (Some(raw), Some(planned)) = (
+                dependency.version.as_deref(),
+                plan.steps.iter().find(|s| s.mod_id == dependency.id),
+            ) else {
+                continue;
+            };
+            let Some(version) = planned.version.as_deref() else {
+                continue;
+            };
+            if !VersionReq::parse(raw)?.matches(&Version::parse(version)) {
+                return Err(format!(
+                    "{} requires {} {}, but {} would be installed",
+                    step.mod_id, dependency.id, raw, version
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Dependency;
+    use crate::manifest::ManifestEntry;
+    use crate::test_support;
+
+    fn config(id: &str, version: &str, dependencies: &[(&str, Option<&str>)]) -> ModConfig {
+        ModConfig {
+            version: Some(version.to_string()),
+            direct_download: Some(format!("https://example.com/{}.zip", id)),
+            dependencies: dependencies
+                .iter()
+                .map(|(id, version)| Dependency {
+                    id: id.to_string(),
+                    version: version.map(str::to_string),
+                })
+                .collect(),
+            ..test_support::config(id, None)
+        }
+    }
+
+    fn installed(mods: &[(&str, &str, bool)]) -> Manifest {
+        let mut manifest = Manifest::default();
+        for (id, version, pinned) in mods {
+            manifest.mods.insert(
+                id.to_string(),
+                ManifestEntry {
+                    id: id.to_string(),
+                    version: version.to_string(),
+                    filename: format!("{}.zip", id),
+                    pinned: *pinned,
+                    ..Default::default()
+                },
+            );
+        }
+        manifest
+    }
+
+    fn target(mod_id: &str) -> Target {
+        Target {
+            mod_id: mod_id.to_string(),
+            version: None,
+            download_url: None,
+        }
+    }
+
+    fn planned_ids(plan: &InstallPlan) -> Vec<&str> {
+        plan.steps.iter().map(|step| step.mod_id.as_str()).collect()
+    }
+
+    #[test]
+    fn dependencies_come_first() {
+        let catalog = [
+            config("traffic", "1.0", &[("career", Some(">=2.6"))]),
+            config("career", "2.7.0", &[]),
+        ];
+
+        let plan = resolve(&target("traffic"), &catalog, &Manifest::default()).unwrap();
+
+        assert_eq!(planned_ids(&plan), ["career", "traffic"]);
+        assert_eq!(plan.steps[1].reason, StepReason::Requested);
+    }
+
+    #[test]
+    fn installed_dependency_in_range_is_kept() {
+        let catalog = [
+            config("traffic", "1.0", &[("career", Some(">=2.6"))]),
+            config("career", "2.7.0", &[]),
+        ];
+
+        let plan = resolve(&target("traffic"), &catalog, &installed(&[("career", "2.6.2", false)])).unwrap();
+
+        assert_eq!(planned_ids(&plan), ["traffic"]);
+        assert_eq!(plan.satisfied.len(), 1);
+        assert_eq!(plan.satisfied[0].installed_version, "2.6.2");
+    }
+
+    #[test]
+    fn cycles_are_reported() {
+        let catalog = [
+            config("a", "1.0", &[("b", None)]),
+            config("b", "1.0", &[("c", None)]),
+            config("c", "1.0", &[("a", None)]),
+        ];
+
+        let err = resolve(&target("a"), &catalog, &Manifest::default()).unwrap_err();
+
+        assert_eq!(err, "Dependency cycle: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn pinned_dependency_is_not_changed() {
+        let catalog = [
+            config("traffic", "1.0", &[("career", Some(">=2.7"))]),
+            config("career", "2.7.0", &[]),
+        ];
+
+        let err = resolve(&target("traffic"), &catalog, &installed(&[("career", "2.6.2", true)])).unwrap_err();
+
+        assert!(err.contains("career is pinned to 2.6.2"), "{}", err);
+    }
+
+    #[test]
+    fn unpinned_dependency_out_of_range_is_updated() {
+        let catalog = [
+            config("traffic", "1.0", &[("career", Some(">=2.7"))]),
+            config("career", "2.7.0", &[]),
+        ];
+
+        let plan = resolve(&target("traffic"), &catalog, &installed(&[("career", "2.6.2", false)])).unwrap();
+
+        assert_eq!(planned_ids(&plan), ["career", "traffic"]);
+        assert_eq!(plan.steps[0].installed_version.as_deref(), Some("2.6.2"));
+    }
+
+    #[test]
+    fn conflicts_apply_in_both_directions() {
+        let mut career = config("career", "2.7.0", &[]);
+        career.conflicts = vec!["old_career".to_string()];
+        let catalog = [career, config("old_career", "1.0", &[])];
+
+        // Declared by the mod being installed.
+        let err = resolve(&target("career"), &catalog, &installed(&[("old_career", "1.0", false)])).unwrap_err();
+        assert_eq!(err, "career (being installed) conflicts with old_career (installed)");
+
+        // Declared by the mod already installed.
+        let err = resolve(&target("old_career"), &catalog, &installed(&[("career", "2.7.0", false)])).unwrap_err();
+        assert_eq!(err, "career (installed) conflicts with old_career (being installed)");
+    }
+
+    #[test]
+    fn resolved_release_must_meet_requirements() {
+        let mut career = config("career", "2.7.0", &[]);
+        career.version = None;
+        career.direct_download = None;
+        let catalog = [config("traffic", "1.0", &[("career", Some(">=2.7"))]), career];
+        let mut plan = resolve(&target("traffic"), &catalog, &Manifest::default()).unwrap();
+        assert_eq!(plan.steps[0].version, None);
+
+        plan.steps[0].version = Some("2.6.9".to_string());
+        let err = check_planned_versions(&plan, &catalog).unwrap_err();
+        assert_eq!(err, "traffic requires career >=2.7, but 2.6.9 would be installed");
+
+        plan.steps[0].version = Some("2.7.1".to_string());
+        assert!(check_planned_versions(&plan, &catalog).is_ok());
+    }
+}
@@ -0,0 +1,221 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use futures_util::StreamExt;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use tauri::Emitter;
+
+use crate::{cache, fsutil};
+
+// File names from Content-Disposition headers are attacker controlled;
+// keep only the last path component so they cannot escape the mods folder.
+pub fn safe_filename(name: &str) -> Option<&str> {
+    let name = name.rsplit(['/', '\\']).next()?.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+#[derive(Serialize, Clone)]
+struct DownloadProgressPayload {
+    mod_id: Option<String>,
+    url: String,
+    downloaded: u64,
+    total: Option<u64>,
+    progress: Option<u8>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct DownloadedFile {
+    pub path: String,
+    pub filename: String,
+    pub sha256: String,
+    pub size: u64,
+    // Restored from the download cache instead of the network.
+    pub cached: bool,
+}
+
+fn emit_progress(window: &tauri::Window, payload: DownloadProgressPayload) {
+    if let Err(e) = window.emit("download_progress", payload) {
+        eprintln!("Failed to emit download_progress event: {}", e);
+    }
+}
+
+fn downloaded_file(path: &Path, sha256: String, size: u64, cached: bool) -> DownloadedFile {
+    DownloadedFile {
+        path: path.to_string_lossy().to_string(),
+        filename: path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default(),
+        sha256,
+        size,
+        cached,
+    }
+}
+
+// Report a file restored from the download cache as a finished download.
+fn restored(window: &tauri::Window, mod_id: Option<String>, url: &str, dest_path: &Path, sha256: String) -> DownloadedFile {
+    let size = fs::metadata(dest_path).map(|m| m.len()).unwrap_or(0);
+    emit_progress(window, DownloadProgressPayload {
+        mod_id,
+        url: url.to_string(),
+        downloaded: size,
+        total: Some(size),
+        progress: Some(100),
+    });
+    downloaded_file(dest_path, sha256, size, true)
+}
+
+// Download `url` to `target_path` (already checked by the caller), emitting
+// `download_progress` events. The server's Content-Disposition filename is
+// preferred over the requested one, in the same directory. When `sha256`
+// is given, a download with any other content is deleted and an error.
+pub async fn download_file(
+    window: &tauri::Window,
+    url: &str,
+    target_path: PathBuf,
+    mod_id: Option<String>,
+    sha256: Option<&str>,
+) -> Result<DownloadedFile, String> {
+    let expected = sha256.map(str::to_lowercase);
+
+    // A known hash identifies the file exactly, so a cached copy needs no
+    // request at all.
+    if let Some(hash) = &expected {
+        if let Some(dest_path) = cache::restore(url, hash, &target_path) {
+            return Ok(restored(window, mod_id, url, &dest_path, hash.clone()));
+        }
+    }
+
+    // Stream a file download from a URL to the disk.
+    // Use a browser-like User-Agent to avoid 403s from some hosts.
+    let client = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+        .timeout(std::time::Duration::from_secs(60))
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    // Otherwise an earlier download of the URL is only reused once the
+    // server confirms it has not changed.
+    let cached_source = if expected.is_none() { cache::source(url) } else { None };
+    let mut request = client.get(url);
+    if let Some(source) = &cached_source {
+        if let Some(etag) = &source.etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &source.last_modified {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    let mut res = request.send().await.map_err(|e| e.to_string())?;
+
+    if res.status() == reqwest::StatusCode::NOT_MODIFIED {
+        if let Some(source) = cached_source {
+            if let Some(dest_path) = cache::restore(url, &source.sha256, &target_path) {
+                return Ok(restored(window, mod_id, url, &dest_path, source.sha256));
+            }
+        }
+        // The cached copy is gone; fetch the file itself.
+        res = client.get(url).send().await.map_err(|e| e.to_string())?;
+    }
+
+    if !res.status().is_success() {
+        return Err(format!("Download failed with status: {}", res.status()));
+    }
+
+    let header = |name: reqwest::header::HeaderName| {
+        res.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG);
+    let last_modified = header(reqwest::header::LAST_MODIFIED);
+    let total_size = res.content_length();
+
+    // Decide final destination path. Default to the requested target_path,
+    // but if the server provides a Content-Disposition filename, prefer
+    // that name in the same directory so we preserve the original zip name.
+    let requested_path = target_path;
+    let mut dest_path = requested_path.clone();
+
+    if let Some(content_disposition) = res.headers().get("content-disposition") {
+        if let Ok(cd_str) = content_disposition.to_str() {
+            if let Some(filename_start) = cd_str.find("filename=\"") {
+                let filename_content = &cd_str[filename_start + 10..];
+                if let Some(filename_end) = filename_content.find('"') {
+                    if let Some(filename) = safe_filename(&filename_content[..filename_end]) {
+                        if let Some(parent) = requested_path.parent() {
+                            dest_path = parent.join(filename);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Ensure parent directory exists
+    if let Some(parent) = dest_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    // Streamed next to the target and only moved over it once complete and
+    // verified, so a failed download never clobbers the installed file.
+    let part_path = fsutil::part_path(&dest_path);
+    let streamed = async {
+        let mut file = fs::File::create(&part_path).map_err(|e| e.to_string())?;
+        let mut stream = res.bytes_stream();
+        let mut hasher = Sha256::new();
+        let mut downloaded: u64 = 0;
+        let mut last_emitted: u8 = 0;
+
+        while let Some(item) = stream.next().await {
+            let chunk = item.map_err(|e| e.to_string())?;
+            let chunk_len = chunk.len() as u64;
+            file.write_all(&chunk).map_err(|e| e.to_string())?;
+            hasher.update(&chunk);
+
+            downloaded = downloaded.saturating_add(chunk_len);
+
+            if let Some(total) = total_size {
+                if total > 0 {
+                    let pct = ((downloaded as f64 / total as f64) * 100.0).round() as u8;
+                    if pct != last_emitted && pct <= 100 {
+                        last_emitted = pct;
+                        emit_progress(window, DownloadProgressPayload {
+                            mod_id: mod_id.clone(),
+                            url: url.to_string(),
+                            downloaded,
+                            total: Some(total),
+                            progress: Some(pct),
+                        });
+                    }
+                }
+            }
+        }
+
+        file.sync_all().map_err(|e| e.to_string())?;
+        Ok::<_, String>((format!("{:x}", hasher.finalize()), downloaded))
+    }
+    .await;
+
+    let finished = streamed.and_then(|(hash, downloaded)| {
+        if let Some(expected) = expected.as_ref().filter(|expected| **expected != hash) {
+            return Err(format!("Downloaded file does not match the expected SHA-256 (expected {}, got {})", expected, hash));
+        }
+        fsutil::move_file_with(&part_path, &dest_path, true).map_err(|e| e.to_string())?;
+        Ok((hash, downloaded))
+    });
+    let (hash, downloaded) = finished.inspect_err(|_| {
+        let _ = fs::remove_file(&part_path);
+    })?;
+
+    let source = cache::UrlSource {
+        sha256: hash.clone(),
+        etag,
+        last_modified,
+    };
+    if let Err(e) = cache::store(&dest_path, url, source) {
+        eprintln!("Failed to cache download {}: {}", url, e);
+    }
+
+    Ok(downloaded_file(&dest_path, hash, downloaded, false))
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+// Replace `path` with `contents` so that a crash at any point leaves
+// either the old or the new file, never a truncated one: write a sibling
+// temp file, flush it to disk, then rename it over the target.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::other(format!("{} has no file name", path.display())))?;
+    let tmp_path = path.with_file_name(format!("{}.tmp", file_name.to_string_lossy()));
+
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+    }
+    fs::rename(&tmp_path, path)?;
+
+    // Persist the rename itself. Directories cannot be opened this way on
+    // Windows, where NTFS journals the rename for us.
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+
+    Ok(())
+}
+
+// Sibling of `path` a download is written to until it is complete and
+// verified. BeamNG only loads `.zip` files, so it never sees a partial one.
+pub fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveMethod {
+    Renamed,
+    // Source and destination were on different volumes: the file was
+    // copied, verified and the source deleted.
+    Copied,
+}
+
+pub fn sha256_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// True when both paths name the same file, e.g. a case-only rename on a
+// case-insensitive file system.
+pub fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn copy_verified(from: &Path, to: &Path, overwrite: bool) -> io::Result<()> {
+    let expected = sha256_file(from)?;
+    {
+        let mut reader = fs::File::open(from)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true);
+        if overwrite {
+            options.create(true).truncate(true);
+        } else {
+            // Fails if something appeared at the destination meanwhile.
+            options.create_new(true);
+        }
+        let mut writer = options.open(to)?;
+        io::copy(&mut reader, &mut writer)?;
+        writer.sync_all()?;
+    }
+
+    if sha256_file(to)? != expected {
+        let _ = fs::remove_file(to);
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Copy of {} to {} does not match the original", from.display(), to.display()),
+        ));
+    }
+    Ok(())
+}
+
+// Move a file without ever replacing an existing destination. Falls back
+// to copy + verify + delete when source and destination live on
+// different volumes (e.g. a mods folder on another drive than
+// LOCALAPPDATA) and a plain rename is not possible.
+pub fn move_file(from: &Path, to: &Path) -> io::Result<MoveMethod> {
+    move_file_with(from, to, false)
+}
+
+pub fn move_file_with(from: &Path, to: &Path, overwrite: bool) -> io::Result<MoveMethod> {
+    // fs::rename replaces the destination on most platforms, so it is only
+    // used directly when that is wanted, or when both paths name the same
+    // file and the move just changes the case of its name.
+    if overwrite || is_same_file(from, to) {
+        return match fs::rename(from, to) {
+            Ok(()) => Ok(MoveMethod::Renamed),
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => copy_then_remove(from, to, overwrite),
+            Err(e) => Err(e),
+        };
+    }
+
+    // Creating the hard link fails atomically when the destination exists,
+    // unlike checking first and renaming after.
+    match fs::hard_link(from, to) {
+        Ok(()) => {
+            if let Err(e) = fs::remove_file(from) {
+                let _ = fs::remove_file(to);
+                return Err(e);
+            }
+            Ok(MoveMethod::Renamed)
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        )),
+        // Other volume, or a file system without hard links (FAT32,
+        // exFAT): the copy is opened with create_new, which is atomic too.
+        Err(_) => copy_then_remove(from, to, false),
+    }
+}
+
+fn copy_then_remove(from: &Path, to: &Path, overwrite: bool) -> io::Result<MoveMethod> {
+    copy_verified(from, to, overwrite)?;
+    if let Err(e) = fs::remove_file(from) {
+        // Do not leave two copies behind if the source is locked.
+        let _ = fs::remove_file(to);
+        return Err(e);
+    }
+    Ok(MoveMethod::Copied)
+}
+
+// Total size in bytes and number of files below `dir`. Entries that
+// cannot be read are skipped.
+pub fn dir_size(dir: &Path) -> (u64, usize) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    entries.flatten().fold((0, 0), |(bytes, files), entry| {
+        let path = entry.path();
+        if path.is_dir() {
+            let (b, f) = dir_size(&path);
+            (bytes + b, files + f)
+        } else {
+            (bytes + entry.metadata().map(|m| m.len()).unwrap_or(0), files + 1)
+        }
+    })
+}
+
+// Run slow file work (zipping, hashing, copies across volumes) on a
+// blocking thread; sync commands run on the main thread and would freeze
+// the window.
+pub async fn blocking<T, F>(work: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(work).await.map_err(|e| e.to_string())?
+}
+
+pub fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,64 @@
+use std::ffi::OsStr;
+use std::path::Path;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+// Executable names BeamNG.drive runs under. The Windows build is also
+// what shows up when the game is launched through Proton/Wine, while
+// the native Linux build drops the .exe suffix.
+const BEAMNG_EXECUTABLES: &[&str] = &[
+    "beamng.drive.x64.exe",
+    "beamng.drive.exe",
+    "beamng.drive.x64",
+];
+
+// Returned by every command that would touch files inside the mods
+// folder while the game may still have them open.
+pub const GAME_RUNNING_ERROR: &str =
+    "BeamNG.drive is running. Close the game before installing, updating or removing mods.";
+
+fn is_beamng_executable(name: &OsStr) -> bool {
+    let name = name.to_string_lossy().to_lowercase();
+    // Wine/Proton command lines carry Windows paths, so split on both
+    // separators instead of relying on Path semantics of the host.
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(&name);
+    BEAMNG_EXECUTABLES.contains(&base)
+}
+
+pub fn is_game_running() -> bool {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_exe(UpdateKind::OnlyIfNotSet),
+    );
+
+    system.processes().values().any(|process| {
+        // On Linux the process name is truncated to 15 characters, so
+        // also look at the executable path and the first argument, which
+        // is where Wine exposes the real Windows executable name.
+        is_beamng_executable(process.name())
+            || process
+                .exe()
+                .and_then(Path::file_name)
+                .is_some_and(is_beamng_executable)
+            || process
+                .cmd()
+                .first()
+                .is_some_and(|arg| is_beamng_executable(arg))
+    })
+}
+
+pub fn ensure_game_not_running() -> Result<(), String> {
+    if is_game_running() {
+        Err(GAME_RUNNING_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub fn is_beamng_running() -> bool {
+    is_game_running()
+}
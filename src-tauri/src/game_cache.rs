@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+
+use crate::{fsutil, game, paths, settings};
+
+// Folders under the BeamNG user folder the game rebuilds on its own. Old
+// unpacked Lua and textures in here can outlive the mod zip they came from.
+const CACHE_DIRS: &[&str] = &["temp", "cache"];
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CacheDir {
+    pub path: String,
+    pub bytes: u64,
+    pub files: usize,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct GameCacheInfo {
+    pub dirs: Vec<CacheDir>,
+    pub clear_after_update: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct CacheFailure {
+    pub path: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct CacheClearReport {
+    // Folders that were emptied, with what they held before.
+    pub cleared: Vec<CacheDir>,
+    pub freed_bytes: u64,
+    // Entries that could not be removed, e.g. locked by another program.
+    pub failed: Vec<CacheFailure>,
+}
+
+fn cache_dirs() -> Result<Vec<CacheDir>, String> {
+    let user_folder = paths::user_folder()?;
+    Ok(CACHE_DIRS
+        .iter()
+        .map(|dir| user_folder.join(dir))
+        .filter(|dir| dir.is_dir())
+        .map(|dir| {
+            let (bytes, files) = fsutil::dir_size(&dir);
+            CacheDir {
+                path: dir.to_string_lossy().to_string(),
+                bytes,
+                files,
+            }
+        })
+        .collect())
+}
+
+// Remove everything inside a cache folder but keep the folder itself.
+fn empty_dir(dir: &Path, report: &mut CacheClearReport) -> Result<(), String> {
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())?.flatten() {
+        let path = entry.path();
+        let (bytes, _) = if path.is_dir() { fsutil::dir_size(&path) } else { (entry.metadata().map(|m| m.len()).unwrap_or(0), 1) };
+        let removed = if path.is_dir() { fs::remove_dir_all(&path) } else { fs::remove_file(&path) };
+        match removed {
+            Ok(()) => report.freed_bytes += bytes,
+            Err(e) => report.failed.push(CacheFailure {
+                path: path.to_string_lossy().to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok(())
+}
+
+pub fn clear() -> Result<CacheClearReport, String> {
+    game::ensure_game_not_running()?;
+    let mut report = CacheClearReport::default();
+    for dir in cache_dirs()? {
+        empty_dir(Path::new(&dir.path), &mut report)?;
+        report.cleared.push(dir);
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn get_game_cache_info() -> Result<GameCacheInfo, String> {
+    Ok(GameCacheInfo {
+        dirs: cache_dirs()?,
+        clear_after_update: settings::load().clear_cache_after_update,
+    })
+}
+
+#[tauri::command]
+pub fn clear_game_cache() -> Result<CacheClearReport, String> {
+    clear()
+}
+
+#[tauri::command]
+pub fn set_clear_cache_after_update(enabled: bool) -> Result<(), String> {
+    settings::update(|settings| settings.clear_cache_after_update = enabled)?;
+    Ok(())
+}
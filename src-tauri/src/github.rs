@@ -0,0 +1,485 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::catalog::ModConfig;
+use crate::channels::{self, Channel};
+use crate::{fsutil, paths, settings};
+
+const DEFAULT_API_BASE: &str = "https://api.github.com";
+
+// Releases fetched per repository; the newest eligible one is picked.
+const RELEASES_PER_PAGE: u32 = 30;
+
+const RESPONSE_CACHE_FILENAME: &str = "github_cache.json";
+
+// Serializes read-modify-write cycles of the response cache.
+static RESPONSE_CACHE_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    // Release notes, in Markdown.
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ResolvedRelease {
+    pub mod_id: String,
+    pub version: String,
+    pub tag: String,
+    pub download_url: String,
+    pub asset_name: String,
+    pub size: u64,
+    pub prerelease: bool,
+    pub published_at: Option<String>,
+    pub html_url: Option<String>,
+}
+
+// Last response for a URL, revalidated with conditional requests; a 304
+// does not count against the GitHub rate limit.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct CachedResponse {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    body: Value,
+    fetched_at: u64,
+}
+
+// Where and how to talk to the GitHub API. The base URL can point at a
+// local mock server.
+pub struct GithubApi {
+    pub api_base: String,
+    pub token: Option<String>,
+    pub cache_path: PathBuf,
+}
+
+impl GithubApi {
+    // API base from RLS_GITHUB_API_URL (for testing), token from settings.
+    pub fn from_settings() -> Result<GithubApi, String> {
+        let api_base = std::env::var("RLS_GITHUB_API_URL")
+            .ok()
+            .filter(|s| !s.is_empty())
+            .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
+        Ok(GithubApi {
+            api_base: api_base.trim_end_matches('/').to_string(),
+            token: settings::load().github_token.filter(|t| !t.is_empty()),
+            cache_path: paths::data_dir()?.join(RESPONSE_CACHE_FILENAME),
+        })
+    }
+
+    fn read_cache(&self) -> BTreeMap<String, CachedResponse> {
+        fs::read_to_string(&self.cache_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn store(&self, url: &str, response: CachedResponse) -> Result<(), String> {
+        let _guard = RESPONSE_CACHE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut cache = self.read_cache();
+        cache.insert(url.to_string(), response);
+        let content = serde_json::to_string(&cache).map_err(|e| e.to_string())?;
+        fsutil::write_atomic(&self.cache_path, content.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    // GET a JSON document, revalidating the cached copy. When GitHub is
+    // unreachable or rate limited, the cached copy is used if there is one.
+    pub async fn get_json(&self, path: &str) -> Result<Value, String> {
+        let url = format!("{}{}", self.api_base, path);
+        let cached = self.read_cache().remove(&url);
+
+        let client = reqwest::Client::builder()
+            .user_agent("RLS-Installer")
+            .timeout(std::time::Duration::from_secs(15))
+            .build()
+            .map_err(|e| e.to_string())?;
+        let mut request = client
+            .get(&url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28");
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        if let Some(cached) = &cached {
+            if let Some(etag) = &cached.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &cached.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return cached.map(|c| c.body).ok_or_else(|| format!("GitHub request failed: {}", e));
+            }
+        };
+        let status = response.status();
+
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            if let Some(mut cached) = cached {
+                cached.fetched_at = fsutil::unix_now();
+                let body = cached.body.clone();
+                if let Err(e) = self.store(&url, cached) {
+                    eprintln!("Failed to update GitHub response cache: {}", e);
+                }
+                return Ok(body);
+            }
+            return Err("GitHub answered 304 without a cached copy".to_string());
+        }
+        if !status.is_success() {
+            let rate_limited = matches!(status.as_u16(), 403 | 429)
+                && response.headers().get("x-ratelimit-remaining").and_then(|v| v.to_str().ok()) == Some("0");
+            let error = if rate_limited {
+                "GitHub rate limit reached; add a GitHub token in settings to raise it".to_string()
+            } else {
+                format!("GitHub request failed with status: {}", status)
+            };
+            return cached.map(|c| c.body).ok_or(error);
+        }
+
+        let header = |name: reqwest::header::HeaderName| {
+            response.headers().get(name).and_then(|v| v.to_str().ok()).map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let last_modified = header(reqwest::header::LAST_MODIFIED);
+        let body: Value = response.json().await.map_err(|e| e.to_string())?;
+
+        let fresh = CachedResponse {
+            etag,
+            last_modified,
+            body: body.clone(),
+            fetched_at: fsutil::unix_now(),
+        };
+        if let Err(e) = self.store(&url, fresh) {
+            eprintln!("Failed to update GitHub response cache: {}", e);
+        }
+        Ok(body)
+    }
+
+    // Recent releases of `owner/repo`, newest first.
+    pub async fn releases(&self, repo: &str) -> Result<Vec<Release>, String> {
+        let body = self
+            .get_json(&format!("/repos/{}/releases?per_page={}", repo, RELEASES_PER_PAGE))
+            .await?;
+        serde_json::from_value(body).map_err(|e| format!("Unexpected GitHub response for {}: {}", repo, e))
+    }
+}
+
+// Tags are usually `v2.6.2`; the manifest stores `2.6.2`.
+pub fn version_from_tag(tag: &str) -> String {
+    match tag.strip_prefix(['v', 'V']) {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest.to_string(),
+        _ => tag.to_string(),
+    }
+}
+
+// Every release with an asset for the mod, newest first. Drafts are
+// skipped. The asset is the first one matching `assetPattern`, or the
+// first zip without a pattern.
+pub fn installable_releases(config: &ModConfig, releases: &[Release]) -> Result<Vec<ResolvedRelease>, String> {
+    let pattern = config
+        .asset_pattern
+        .as_deref()
+        .map(|p| regex::RegexBuilder::new(p).case_insensitive(true).build())
+        .transpose()
+        .map_err(|e| format!("Invalid assetPattern for {}: {}", config.id, e))?;
+    let is_wanted = |asset: &&ReleaseAsset| match &pattern {
+        Some(pattern) => pattern.is_match(&asset.name),
+        None => asset.name.to_lowercase().ends_with(".zip"),
+    };
+
+    Ok(releases
+        .iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| {
+            let asset = release.assets.iter().find(is_wanted)?;
+            Some(ResolvedRelease {
+                mod_id: config.id.clone(),
+                version: version_from_tag(&release.tag_name),
+                tag: release.tag_name.clone(),
+                download_url: asset.browser_download_url.clone(),
+                asset_name: asset.name.clone(),
+                size: asset.size,
+                prerelease: release.prerelease,
+                published_at: release.published_at.clone(),
+                html_url: release.html_url.clone(),
+            })
+        })
+        .collect())
+}
+
+// Newest release with an asset for the mod; pre-releases only count when
+// `include_prereleases` is set.
+pub fn pick_release(config: &ModConfig, releases: &[Release], include_prereleases: bool) -> Result<ResolvedRelease, String> {
+    installable_releases(config, releases)?
+        .into_iter()
+        .find(|release| include_prereleases || !release.prerelease)
+        .ok_or_else(|| format!("No release of {} has an asset for {}", config.github_repo.as_deref().unwrap_or_default(), config.id))
+}
+
+pub async fn resolve(api: &GithubApi, config: &ModConfig, include_prereleases: bool) -> Result<ResolvedRelease, String> {
+    let repo = config
+        .github_repo
+        .as_deref()
+        .ok_or_else(|| format!("{} has no githubRepo", config.id))?;
+    let releases = api.releases(repo).await?;
+    pick_release(config, &releases, include_prereleases)
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ReleaseLookup {
+    pub mod_id: String,
+    pub release: Option<ResolvedRelease>,
+    pub error: Option<String>,
+}
+
+// Resolve the latest release of every catalog entry with a `githubRepo`.
+// Pre-releases count for mods on the beta channel.
+#[tauri::command]
+pub async fn resolve_github_releases(catalog: Vec<ModConfig>, include_prereleases: Option<bool>) -> Result<Vec<ReleaseLookup>, String> {
+    let api = GithubApi::from_settings()?;
+    let include_prereleases = include_prereleases.unwrap_or(false);
+
+    let mut lookups = Vec::new();
+    for config in catalog.iter().filter(|c| c.github_repo.is_some()) {
+        let prereleases = include_prereleases || channels::preferred(&config.id) == Channel::Beta;
+        let (release, error) = match resolve(&api, config, prereleases).await {
+            Ok(release) => (Some(release), None),
+            Err(e) => {
+                eprintln!("GitHub check failed for {}: {}", config.id, e);
+                (None, Some(e))
+            }
+        };
+        lookups.push(ReleaseLookup {
+            mod_id: config.id.clone(),
+            release,
+            error,
+        });
+    }
+    Ok(lookups)
+}
+
+#[tauri::command]
+pub fn has_github_token() -> bool {
+    settings::load().github_token.is_some_and(|t| !t.is_empty())
+}
+
+// Store a personal access token to raise the GitHub rate limit; None or
+// an empty string removes it. The token is never sent back to the UI.
+#[tauri::command]
+pub fn set_github_token(token: Option<String>) -> Result<(), String> {
+    let token = token.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    settings::update(|settings| settings.github_token = token)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{self, TempDir};
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset {
+            name: name.to_string(),
+            browser_download_url: format!("https://example.com/{}", name),
+            size: 1,
+        }
+    }
+
+    fn release(tag: &str, prerelease: bool, assets: &[&str]) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            name: None,
+            draft: false,
+            prerelease,
+            published_at: None,
+            html_url: None,
+            body: None,
+            assets: assets.iter().map(|name| asset(name)).collect(),
+        }
+    }
+
+    fn config(asset_pattern: Option<&str>) -> ModConfig {
+        ModConfig {
+            github_repo: Some("owner/career".to_string()),
+            ..test_support::config("career", asset_pattern)
+        }
+    }
+
+    #[test]
+    fn asset_pattern_picks_the_asset() {
+        let releases = [
+            release("v2.7.0", false, &["traffic_2.7.0.zip", "Career_2.7.0.zip"]),
+            release("v2.6.9", false, &["traffic_2.6.9.zip"]),
+        ];
+
+        let installable = installable_releases(&config(Some(r"^career_.*\.zip$")), &releases).unwrap();
+
+        assert_eq!(installable.len(), 1);
+        assert_eq!(installable[0].asset_name, "Career_2.7.0.zip");
+        assert_eq!(installable[0].version, "2.7.0");
+        assert_eq!(installable[0].tag, "v2.7.0");
+    }
+
+    #[test]
+    fn first_zip_without_a_pattern() {
+        let releases = [release("2.7.0", false, &["notes.txt", "career.zip", "extra.zip"])];
+
+        let installable = installable_releases(&config(None), &releases).unwrap();
+
+        assert_eq!(installable[0].asset_name, "career.zip");
+        assert_eq!(installable[0].version, "2.7.0");
+    }
+
+    #[test]
+    fn drafts_and_bad_patterns() {
+        let mut draft = release("v2.8.0", false, &["career.zip"]);
+        draft.draft = true;
+        let releases = [draft, release("v2.7.0", false, &["career.zip"])];
+
+        let installable = installable_releases(&config(None), &releases).unwrap();
+        assert_eq!(installable.len(), 1);
+        assert_eq!(installable[0].tag, "v2.7.0");
+
+        assert!(installable_releases(&config(Some("(")), &releases).is_err());
+    }
+
+    #[test]
+    fn pre_releases_only_when_asked() {
+        let releases = [
+            release("v2.8.0-beta1", true, &["career.zip"]),
+            release("v2.7.0", false, &["career.zip"]),
+        ];
+
+        assert_eq!(pick_release(&config(None), &releases, false).unwrap().version, "2.7.0");
+        let beta = pick_release(&config(None), &releases, true).unwrap();
+        assert_eq!(beta.version, "2.8.0-beta1");
+        assert!(beta.prerelease);
+
+        let only_beta = [release("v2.8.0-beta1", true, &["career.zip"])];
+        assert!(pick_release(&config(None), &only_beta, false).is_err());
+    }
+
+    #[test]
+    fn tags_to_versions() {
+        assert_eq!(version_from_tag("v2.6.2"), "2.6.2");
+        assert_eq!(version_from_tag("V2.6.2_hotfix"), "2.6.2_hotfix");
+        assert_eq!(version_from_tag("2.6.2"), "2.6.2");
+        assert_eq!(version_from_tag("velocity"), "velocity");
+    }
+
+    // Serves `responses` in order, one per connection, and sends back each
+    // raw request it received.
+    fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, requests) = mpsc::channel();
+        std::thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).unwrap();
+                    if n == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..n]);
+                }
+                sender.send(String::from_utf8_lossy(&request).to_lowercase()).unwrap();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (base, requests)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {}\r\nconnection: close\r\ncontent-length: {}\r\n", status, body.len());
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str("\r\n");
+        response.push_str(body);
+        response
+    }
+
+    fn api(base: String, name: &str) -> (GithubApi, TempDir) {
+        let dir = TempDir::new(name);
+        let api = GithubApi {
+            api_base: base,
+            token: None,
+            cache_path: dir.join(RESPONSE_CACHE_FILENAME),
+        };
+        (api, dir)
+    }
+
+    #[tokio::test]
+    async fn not_modified_reuses_the_cached_body() {
+        let (base, requests) = serve(vec![
+            response("200 OK", &["etag: \"abc\"", "content-type: application/json"], r#"{"tag_name":"v1"}"#),
+            response("304 Not Modified", &["etag: \"abc\""], ""),
+        ]);
+        let (api, _dir) = api(base, "etag");
+
+        let first = api.get_json("/repos/owner/career/releases").await.unwrap();
+        assert!(!requests.recv().unwrap().contains("if-none-match"));
+
+        let second = api.get_json("/repos/owner/career/releases").await.unwrap();
+        assert!(requests.recv().unwrap().contains("if-none-match: \"abc\""));
+        assert_eq!(first, second);
+        assert_eq!(second["tag_name"], "v1");
+    }
+
+    #[tokio::test]
+    async fn rate_limit_is_reported() {
+        let (base, _requests) = serve(vec![response("403 Forbidden", &["x-ratelimit-remaining: 0"], "{}")]);
+        let (api, _dir) = api(base, "ratelimit");
+
+        let error = api.get_json("/repos/owner/career/releases").await.unwrap_err();
+        assert!(error.contains("rate limit"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn rate_limit_falls_back_to_the_cache() {
+        let (base, _requests) = serve(vec![
+            response("200 OK", &["etag: \"abc\""], r#"{"tag_name":"v1"}"#),
+            response("429 Too Many Requests", &["x-ratelimit-remaining: 0"], "{}"),
+        ]);
+        let (api, _dir) = api(base, "ratelimit-cached");
+
+        api.get_json("/repos/owner/career/releases").await.unwrap();
+        let cached = api.get_json("/repos/owner/career/releases").await.unwrap();
+        assert_eq!(cached["tag_name"], "v1");
+    }
+}
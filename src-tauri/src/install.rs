@@ -0,0 +1,371 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+
+use crate::catalog::{self, ModConfig};
+use crate::channels::{self, Channel};
+use crate::deps::{self, InstallPlan, PlanStep, StepReason, Target};
+use crate::archive::{self, ArchivedVersion};
+use crate::download::{self, DownloadedFile};
+use crate::github::{self, GithubApi};
+use crate::manifest::{self, Manifest, ManifestEntry};
+use crate::{fsutil, paths, profiles, reconcile, saves};
+use crate::removal::{self, DeleteOutcome, FileOwner, RemovalMode};
+use crate::trash::{self, TrashEntry};
+use crate::version::Version;
+use crate::game_cache::{self, CacheClearReport};
+use crate::{game, settings};
+
+// Downloads wait here until they replace the installed version.
+const STAGING_DIRNAME: &str = "downloads";
+
+#[derive(Serialize, Clone, Debug)]
+pub struct InstallResult {
+    pub plan: InstallPlan,
+    // Manifest entries written, in installation order.
+    pub installed: Vec<ManifestEntry>,
+    pub manifest: Manifest,
+    // Set when the install replaced a mod and clearing the game cache
+    // after updates is enabled.
+    pub cache_cleared: Option<CacheClearReport>,
+}
+
+pub fn ensure_removed(outcome: DeleteOutcome) -> Result<(), String> {
+    match outcome {
+        DeleteOutcome::Locked { path, error, .. } => Err(format!("{} is in use by another program ({})", path, error)),
+        DeleteOutcome::Failed { path, error } => Err(format!("Could not delete {}: {}", path, error)),
+        _ => Ok(()),
+    }
+}
+
+// Name to save a download under until the server tells us better.
+fn guessed_filename(step: &PlanStep, url: &str) -> String {
+    let from_url = url.split(['?', '#']).next().unwrap_or(url).rsplit('/').next().unwrap_or_default();
+    // Links like `.../file?h=...` carry no usable name at all.
+    let name = match download::safe_filename(from_url) {
+        Some(name) if name.to_lowercase().ends_with(".zip") => name.to_string(),
+        _ => format!("{}_{}.zip", step.mod_id, step.version.as_deref().unwrap_or("latest")),
+    };
+    name.chars()
+        .map(|c| if matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .collect()
+}
+
+// Empty directory to download one mod into before it replaces anything.
+fn staging_dir() -> Result<PathBuf, String> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let dir = paths::data_dir()?.join(STAGING_DIRNAME).join(format!("{:x}", nanos));
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+// Replace whatever is installed for one mod with the planned version:
+// the new zip is downloaded first, then career saves are backed up when a
+// core mod changes, the current zip is archived, stray zips matching the
+// mod's pattern go to the trash, and the new zip is moved in and recorded
+// in the manifest. A failed download leaves the installed version alone,
+// and a failure after that puts the archived and trashed files back.
+// The pin is kept unless `pinned` says otherwise. With `sha256`, a
+// download with other contents fails the step.
+async fn install_step(
+    window: &tauri::Window,
+    step: &PlanStep,
+    config: &ModConfig,
+    pinned: Option<bool>,
+    sha256: Option<&str>,
+) -> Result<ManifestEntry, String> {
+    let staging = staging_dir()?;
+    let result = replace_installed(window, step, config, pinned, sha256, &staging).await;
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        eprintln!("Failed to clean up download folder {:?}: {}", staging, e);
+    }
+    result
+}
+
+async fn replace_installed(
+    window: &tauri::Window,
+    step: &PlanStep,
+    config: &ModConfig,
+    pinned: Option<bool>,
+    sha256: Option<&str>,
+    staging: &Path,
+) -> Result<ManifestEntry, String> {
+    let url = step
+        .download_url
+        .clone()
+        .filter(|url| !url.is_empty())
+        .ok_or_else(|| format!("No download URL for {}", step.mod_id))?;
+    let downloaded = download::download_file(window, &url, staging.join(guessed_filename(step, &url)), Some(step.mod_id.clone()), sha256).await?;
+    let current = manifest::load(&manifest::manifest_path()?)?;
+
+    if let Some(entry) = current.mods.get(&step.mod_id) {
+        // Career saves may not survive a new version of a core mod.
+        if config.category.as_deref() == Some("core") {
+            let to = step.version.as_deref().unwrap_or("latest");
+            saves::backup_before_update(&config.name, &entry.version, to)?;
+        }
+    }
+
+    let mut undo = Undo::default();
+    let result = swap_in(step, config, pinned, &url, &downloaded, &current, &mut undo).await;
+    if result.is_err() {
+        undo.run();
+    }
+    result
+}
+
+// What `swap_in` changed so far, to put back when a later step fails.
+#[derive(Default)]
+struct Undo {
+    // The archived previous version and where it was.
+    archived: Option<(ArchivedVersion, PathBuf)>,
+    trashed: Vec<TrashEntry>,
+    // The new zip, once moved into the mods folder.
+    moved_in: Option<PathBuf>,
+}
+
+impl Undo {
+    fn run(self) {
+        if let Some(path) = self.moved_in {
+            if let Err(e) = fs::remove_file(&path) {
+                eprintln!("Failed to remove {:?} after install error: {}", path, e);
+            }
+        }
+        for entry in self.trashed.into_iter().rev() {
+            if let Err(e) = trash::restore_from_trash(entry.id) {
+                eprintln!("Failed to restore {} from the trash after install error: {}", entry.filename, e);
+            }
+        }
+        if let Some((archived, path)) = self.archived {
+            if let Err(e) = archive::unarchive(&archived, &path) {
+                eprintln!("Failed to restore {} from the archive after install error: {}", archived.filename, e);
+            }
+        }
+    }
+}
+
+async fn swap_in(
+    step: &PlanStep,
+    config: &ModConfig,
+    pinned: Option<bool>,
+    url: &str,
+    downloaded: &DownloadedFile,
+    current: &Manifest,
+    undo: &mut Undo,
+) -> Result<ManifestEntry, String> {
+    let mods_folder = &settings::mods_folder()?;
+    if let Some(entry) = current.mods.get(&step.mod_id) {
+        let owner = FileOwner {
+            mod_id: Some(step.mod_id.clone()),
+            version: Some(entry.version.clone()),
+        };
+        // A mod disabled by a profile lives in the inactive store.
+        let mut path = mods_folder.join(&entry.filename);
+        let parked = profiles::inactive_dir()?.join(&entry.filename);
+        if !path.exists() && parked.exists() {
+            path = parked;
+        }
+        if path.exists() {
+            let outcome = removal::delete_file(&path.to_string_lossy(), RemovalMode::Archive, owner, false).await;
+            if let DeleteOutcome::Archived { entry, .. } = &outcome {
+                undo.archived = Some((entry.clone(), path));
+            }
+            ensure_removed(outcome)?;
+        }
+    }
+
+    // Other versions of the same mod left in the folder would load too,
+    // and an untracked file under the new name would be in the way.
+    let pattern = config
+        .asset_pattern
+        .as_deref()
+        .map(|pattern| regex::RegexBuilder::new(pattern).case_insensitive(true).build())
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let tracked_elsewhere = |file: &str| current.mods.iter().any(|(id, e)| *id != step.mod_id && e.filename == file);
+    for file in reconcile::list_zip_files(mods_folder)? {
+        let stray = pattern.as_ref().is_some_and(|pattern| pattern.is_match(&file)) && !tracked_elsewhere(&file);
+        if stray || file.eq_ignore_ascii_case(&downloaded.filename) {
+            let owner = FileOwner {
+                mod_id: Some(step.mod_id.clone()),
+                version: None,
+            };
+            let path = mods_folder.join(&file).to_string_lossy().to_string();
+            let outcome = removal::delete_file(&path, RemovalMode::Trash, owner, false).await;
+            if let DeleteOutcome::Trashed { entry, .. } = &outcome {
+                undo.trashed.push(entry.clone());
+            }
+            ensure_removed(outcome)?;
+        }
+    }
+
+    let dest = mods_folder.join(&downloaded.filename);
+    fsutil::move_file(Path::new(&downloaded.path), &dest)
+        .map_err(|e| format!("Could not move {} into the mods folder: {}", downloaded.filename, e))?;
+    undo.moved_in = Some(dest);
+
+    let pinned = pinned.unwrap_or_else(|| current.mods.get(&step.mod_id).is_some_and(|entry| entry.pinned));
+    let entry = ManifestEntry {
+        version: step.version.clone().unwrap_or_else(|| "Unknown".to_string()),
+        filename: downloaded.filename.clone(),
+        source_url: Some(url.to_string()),
+        sha256: Some(downloaded.sha256.clone()),
+        size: Some(downloaded.size),
+        installed_by_installer: true,
+        pinned,
+        ..Default::default()
+    };
+    let saved = manifest::upsert_manifest_entry(step.mod_id.clone(), entry)?;
+    saved
+        .mods
+        .get(&step.mod_id)
+        .cloned()
+        .ok_or_else(|| format!("{} is missing from the manifest after install", step.mod_id))
+}
+
+// Install a mod together with any missing dependencies. Nothing is
+// touched when the plan cannot be resolved, e.g. because of a conflict.
+// Catalog entries follow the user's release channel; `version` and
+// `download_url` override the entry of the requested mod when the caller
+// resolved a newer release or picked an older one. `pin` sets or clears the
+// pin of the requested mod, and `sha256` is the hash its download must
+// have. The catalog is the one the backend loaded, not
+// a copy from the webview.
+#[tauri::command]
+pub async fn install_mod(
+    window: tauri::Window,
+    mod_id: String,
+    version: Option<String>,
+    download_url: Option<String>,
+    pin: Option<bool>,
+    sha256: Option<String>,
+) -> Result<InstallResult, String> {
+    game::ensure_game_not_running()?;
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let catalog = channels::apply_preferences(catalog::loaded());
+    let target = Target {
+        mod_id,
+        version,
+        download_url,
+    };
+    let mut plan = deps::resolve(&target, &catalog, &installed)?;
+
+    // Dependencies published as GitHub releases have no direct link and
+    // only get a version here. Resolve them all before installing anything
+    // so a release that breaks a version requirement stops the whole plan.
+    for step in &mut plan.steps {
+        let config = catalog
+            .iter()
+            .find(|c| c.id == step.mod_id)
+            .ok_or_else(|| format!("{} is not in the catalog", step.mod_id))?;
+        if step.download_url.as_deref().is_none_or(str::is_empty) && config.github_repo.is_some() {
+            let prereleases = channels::preferred(&config.id) == Channel::Beta;
+            let release = github::resolve(&GithubApi::from_settings()?, config, prereleases).await?;
+            step.version = Some(release.version);
+            step.download_url = Some(release.download_url);
+        }
+    }
+    deps::check_planned_versions(&plan, &catalog)?;
+
+    let mut entries = Vec::new();
+    for step in &plan.steps {
+        let config = catalog
+            .iter()
+            .find(|c| c.id == step.mod_id)
+            .ok_or_else(|| format!("{} is not in the catalog", step.mod_id))?;
+        let (pinned, sha256) = if step.reason == StepReason::Requested { (pin, sha256.as_deref()) } else { (None, None) };
+        let entry = install_step(&window, step, config, pinned, sha256)
+            .await
+            .map_err(|e| format!("Installing {} failed: {}", step.name, e))?;
+        entries.push(entry);
+    }
+
+    // An update failing to clear the cache is still a finished update.
+    let replaced = plan.steps.iter().any(|step| step.installed_version.is_some());
+    let cache_cleared = if replaced && settings::load().clear_cache_after_update {
+        game_cache::clear()
+            .inspect_err(|e| eprintln!("Failed to clear the game cache after updating: {}", e))
+            .ok()
+    } else {
+        None
+    };
+
+    Ok(InstallResult {
+        plan,
+        installed: entries,
+        manifest: manifest::load(&manifest::manifest_path()?)?,
+        cache_cleared,
+    })
+}
+
+// Show what `install_mod` would do without touching anything.
+#[tauri::command]
+pub fn plan_install(
+    mod_id: String,
+    version: Option<String>,
+    download_url: Option<String>,
+) -> Result<InstallPlan, String> {
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let catalog = channels::apply_preferences(catalog::loaded());
+    let target = Target {
+        mod_id,
+        version,
+        download_url,
+    };
+    deps::resolve(&target, &catalog, &installed)
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct InstallableVersion {
+    pub version: String,
+    pub download_url: String,
+    pub prerelease: bool,
+    pub published_at: Option<String>,
+}
+
+// Every version of a mod that can be installed, newest first: the
+// catalog's stable and channel builds and its version history, plus all
+// GitHub releases with a matching asset.
+pub async fn installable_versions(config: &ModConfig) -> Result<Vec<InstallableVersion>, String> {
+    // Builds of channels other than stable count as pre-releases.
+    let channel_builds = config.channels.iter().map(|(channel, build)| (build.clone(), *channel != Channel::Stable));
+    let mut versions: Vec<InstallableVersion> = channels::build_for(config, Channel::Stable)
+        .map(|build| (build, false))
+        .into_iter()
+        .chain(channel_builds)
+        .chain(config.versions.iter().map(|build| (build.clone(), false)))
+        .filter_map(|(build, prerelease)| {
+            Some(InstallableVersion {
+                download_url: build.direct_download.filter(|url| !url.is_empty())?,
+                version: build.version,
+                prerelease,
+                published_at: None,
+            })
+        })
+        .collect();
+
+    if let Some(repo) = config.github_repo.as_deref() {
+        let releases = GithubApi::from_settings()?.releases(repo).await?;
+        versions.extend(github::installable_releases(config, &releases)?.into_iter().map(|release| InstallableVersion {
+            version: release.version,
+            download_url: release.download_url,
+            prerelease: release.prerelease,
+            published_at: release.published_at,
+        }));
+    }
+
+    let mut seen = BTreeSet::new();
+    versions.retain(|v| seen.insert(v.version.clone()));
+    versions.sort_by_cached_key(|v| std::cmp::Reverse(Version::parse(&v.version)));
+    Ok(versions)
+}
+
+#[tauri::command]
+pub async fn list_mod_versions(mod_id: String) -> Result<Vec<InstallableVersion>, String> {
+    let config = catalog::find_loaded(&mod_id).ok_or_else(|| format!("{} is not in the loaded catalog", mod_id))?;
+    installable_versions(&config).await
+}
@@ -1,9 +1,37 @@
-use std::path::PathBuf;
 use std::fs;
-use std::io::{Write, ErrorKind};
+use std::io::Write;
 use futures_util::StreamExt;
 use serde::Serialize;
-use tauri::Emitter;
+
+mod archive;
+mod backups;
+mod cache;
+mod catalog;
+mod catalog_sources;
+mod changelog;
+mod channels;
+mod deps;
+mod download;
+mod fsutil;
+mod game;
+mod game_cache;
+mod github;
+mod install;
+mod manifest;
+mod migration;
+mod modpack;
+mod mods_backup;
+mod paths;
+mod profiles;
+mod reconcile;
+mod removal;
+mod saves;
+mod settings;
+#[cfg(test)]
+mod test_support;
+mod trash;
+mod updates;
+mod version;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
@@ -11,12 +39,7 @@ use tauri::Emitter;
 fn detect_beamng_user_path() -> Result<String, String> {
     // Attempt to automatically find the BeamNG user folder on Windows
     // %LOCALAPPDATA%/BeamNG.drive/latest/mods
-    let local_app_data = std::env::var("LOCALAPPDATA").map_err(|e| e.to_string())?;
-    let path = PathBuf::from(local_app_data)
-        .join("BeamNG")
-        .join("BeamNG.drive")
-        .join("current") // "latest" is often a symlink or folder in BeamNG user path
-        .join("mods");
+    let path = paths::default_mods_folder()?;
     // Consider creating it if it doesn't exist?
     // For now, just return the path.
     if !path.exists() {
@@ -29,46 +52,32 @@ fn detect_beamng_user_path() -> Result<String, String> {
     Ok(path.to_string_lossy().to_string())
 }
 
+// Removed mods go to the installer trash by default so an accidental
+// delete can be undone. Updates archive the replaced version instead so
+// it stays available for rollbacks.
 #[tauri::command]
-fn delete_old_mod(file_path: String) -> Result<String, String> {
-    let path = PathBuf::from(&file_path);
-    if !path.exists() {
-        // If it doesn't exist, that's fine too, essentially "deleted"
-        return Ok(format!("File {} did not exist", file_path));
-    }
-
-    match fs::remove_file(&path) {
-        Ok(_) => Ok(format!("Deleted {}", file_path)),
-        Err(e) => {
-            // If Windows reports access denied but the file is effectively
-            // gone or will be removed shortly, we do not want to surface a
-            // scary error to the user. Treat PermissionDenied as a soft
-            // success and let the front-end refresh its view.
-            if e.kind() == ErrorKind::PermissionDenied {
-                return Ok(format!(
-                    "Delete reported access denied for {}, continuing", file_path
-                ));
-            }
-
-            // On Windows it's possible to see transient errors even when the
-            // file ultimately disappears. If the file is gone after the
-            // error, treat this as a success to avoid noisy false failures.
-            if !path.exists() {
-                Ok(format!("File {} was already deleted", file_path))
-            } else {
-                Err(e.to_string())
+async fn delete_old_mod(
+    file_path: String,
+    mod_id: Option<String>,
+    version: Option<String>,
+    mode: Option<removal::RemovalMode>,
+    schedule_if_locked: Option<bool>,
+) -> Result<removal::DeleteOutcome, String> {
+    game::ensure_game_not_running()?;
+    let mut file_path = paths::resolve_writable(&file_path)?;
+    // A mod disabled by a profile lives in the inactive store.
+    if !file_path.exists() {
+        if let Some(name) = file_path.file_name() {
+            let parked = profiles::inactive_dir()?.join(name);
+            if parked.exists() {
+                file_path = parked;
             }
         }
     }
-}
+    let file_path = file_path.to_string_lossy().to_string();
 
-#[derive(Serialize, Clone)]
-struct DownloadProgressPayload {
-    mod_id: Option<String>,
-    url: String,
-    downloaded: u64,
-    total: Option<u64>,
-    progress: Option<u8>,
+    let owner = removal::FileOwner { mod_id, version };
+    Ok(removal::delete_file(&file_path, mode.unwrap_or_default(), owner, schedule_if_locked.unwrap_or(false)).await)
 }
 
 #[tauri::command]
@@ -77,96 +86,20 @@ async fn download_mod(
     url: String,
     target_path: String,
     mod_id: Option<String>,
+    sha256: Option<String>,
 ) -> Result<String, String> {
-    // Stream a file download from a URL to the disk.
-    // Use a browser-like User-Agent to avoid 403s from some hosts.
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
-        .timeout(std::time::Duration::from_secs(60))
-        .build()
-        .map_err(|e| e.to_string())?;
-
-    let res = client.get(&url).send().await.map_err(|e| e.to_string())?;
+    game::ensure_game_not_running()?;
+    let target_path = paths::resolve_writable(&target_path)?;
 
-    if !res.status().is_success() {
-        return Err(format!("Download failed with status: {}", res.status()));
-    }
-
-    let total_size = res.content_length();
-
-    // Decide final destination path. Default to the requested target_path,
-    // but if the server provides a Content-Disposition filename, prefer
-    // that name in the same directory so we preserve the original zip name.
-    let requested_path = PathBuf::from(&target_path);
-    let mut dest_path = requested_path.clone();
-
-    if let Some(content_disposition) = res.headers().get("content-disposition") {
-        if let Ok(cd_str) = content_disposition.to_str() {
-            if let Some(filename_start) = cd_str.find("filename=\"") {
-                let filename_content = &cd_str[filename_start + 10..];
-                if let Some(filename_end) = filename_content.find('"') {
-                    let filename = &filename_content[..filename_end];
-                    if !filename.is_empty() {
-                        if let Some(parent) = requested_path.parent() {
-                            dest_path = parent.join(filename);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Ensure parent directory exists
-    if let Some(parent) = dest_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
-
-    let mut file = fs::File::create(&dest_path).map_err(|e| e.to_string())?;
-    let mut stream = res.bytes_stream();
-    let mut downloaded: u64 = 0;
-    let mut last_emitted: u8 = 0;
-
-    while let Some(item) = stream.next().await {
-        let chunk = item.map_err(|e| e.to_string())?;
-        let chunk_len = chunk.len() as u64;
-        file.write_all(&chunk).map_err(|e| e.to_string())?;
-
-        downloaded = downloaded.saturating_add(chunk_len);
-
-        if let Some(total) = total_size {
-            if total > 0 {
-                let pct = ((downloaded as f64 / total as f64) * 100.0).round() as u8;
-                if pct != last_emitted && pct <= 100 {
-                    last_emitted = pct;
-                    let payload = DownloadProgressPayload {
-                        mod_id: mod_id.clone(),
-                        url: url.clone(),
-                        downloaded,
-                        total: Some(total),
-                        progress: Some(pct),
-                    };
-                    if let Err(e) = window.emit("download_progress", payload) {
-                        eprintln!("Failed to emit download_progress event: {}", e);
-                    }
-                }
-            }
-        }
-    }
-
-    let response = serde_json::json!({
-        "path": dest_path.to_string_lossy(),
-        "filename": dest_path
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_default()
-    })
-    .to_string();
-
-    Ok(response)
+    let downloaded = download::download_file(&window, &url, target_path, mod_id, sha256.as_deref()).await?;
+    serde_json::to_string(&downloaded).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 async fn download_mod_with_auth(url: String, target_path: String, auth_token: Option<String>) -> Result<String, String> {
+    game::ensure_game_not_running()?;
+    let dest_path = paths::resolve_writable(&target_path)?;
+
     // Stream a file download from a URL to the disk, with optional OAuth Bearer token
     // This is used for Patreon downloads that require authentication
     // Returns JSON: {"path": "...", "filename": "..."}
@@ -198,36 +131,47 @@ async fn download_mod_with_auth(url: String, target_path: String, auth_token: Op
             if let Some(filename_start) = cd_str.find("filename=\"") {
                 let filename_content = &cd_str[filename_start + 10..];
                 if let Some(filename_end) = filename_content.find('"') {
-                    actual_filename = Some(filename_content[..filename_end].to_string());
+                    actual_filename = download::safe_filename(&filename_content[..filename_end]).map(str::to_string);
                 }
             }
         }
     }
 
-    let dest_path = PathBuf::from(&target_path);
-    
     // Ensure parent directory exists
     if let Some(parent) = dest_path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    let mut file = fs::File::create(&dest_path).map_err(|e| e.to_string())?;
-    let mut stream = res.bytes_stream();
+    // Written to a .part file first so a failed download leaves any
+    // existing file at the target untouched.
+    let part_path = fsutil::part_path(&dest_path);
+    let streamed = async {
+        let mut file = fs::File::create(&part_path).map_err(|e| e.to_string())?;
+        let mut stream = res.bytes_stream();
 
-    while let Some(item) = stream.next().await {
-        let chunk = item.map_err(|e| e.to_string())?;
-        file.write_all(&chunk).map_err(|e| e.to_string())?;
+        while let Some(item) = stream.next().await {
+            let chunk = item.map_err(|e| e.to_string())?;
+            file.write_all(&chunk).map_err(|e| e.to_string())?;
+        }
+        file.sync_all().map_err(|e| e.to_string())
     }
+    .await;
+    streamed
+        .and_then(|()| fsutil::move_file_with(&part_path, &dest_path, true).map_err(|e| e.to_string()))
+        .inspect_err(|_| {
+            let _ = fs::remove_file(&part_path);
+        })?;
+    let path = dest_path.to_string_lossy();
 
     // Return JSON with actual filename if available
     let response = if let Some(filename) = actual_filename {
         serde_json::json!({
-            "path": target_path,
+            "path": path,
             "filename": filename
         }).to_string()
     } else {
         serde_json::json!({
-            "path": target_path,
+            "path": path,
             "filename": null
         }).to_string()
     };
@@ -235,76 +179,72 @@ async fn download_mod_with_auth(url: String, target_path: String, auth_token: Op
     Ok(response)
 }
 
+// Raw manifest I/O. Both directions go through the typed model so legacy
+// documents are migrated and malformed content is rejected before it
+// reaches the disk.
 #[tauri::command]
 fn save_manifest(path: String, content: String) -> Result<(), String> {
-    fs::write(path, content).map_err(|e| e.to_string())
+    let path = paths::resolve_manifest(&path)?;
+    let manifest = manifest::parse(&content)?;
+    manifest::save(&path, &manifest)
 }
 
+// Falls back to the newest valid backup when the file is damaged, or to an
+// empty manifest without one; the returned document then carries
+// `recovered_from` with the backup used, or "empty".
+// Only the live manifest qualifies, since recovering rewrites the file.
 #[tauri::command]
 fn read_manifest(path: String) -> Result<String, String> {
-    fs::read_to_string(path).map_err(|e| e.to_string())
+    let loaded = manifest::load_recovering(&paths::resolve_manifest(&path)?)?;
+    serde_json::to_string_pretty(&loaded).map_err(|e| e.to_string())
 }
 
 // Return the path to mod_manifest.json located in a per-user
-// RLS Installer data directory under LOCALAPPDATA. If an older
-// manifest exists next to the executable (from previous versions
-// installed under Program Files), attempt a one-time migration.
+// RLS Installer data directory under LOCALAPPDATA. Manifests left in
+// older locations are migrated at startup, see migration.rs.
 #[tauri::command]
 fn get_manifest_path() -> Result<String, String> {
-    let local_app_data = std::env::var("LOCALAPPDATA").map_err(|e| e.to_string())?;
-    let data_dir = PathBuf::from(local_app_data).join("RLS Installer");
-
-    if let Err(e) = fs::create_dir_all(&data_dir) {
-        return Err(e.to_string());
-    }
-
-    let manifest_path = data_dir.join("mod_manifest.json");
-
-    // One-time migration from old location next to the executable
-    if !manifest_path.exists() {
-        if let Ok(exe) = std::env::current_exe() {
-            if let Some(exe_dir) = exe.parent() {
-                let old_manifest = exe_dir.join("mod_manifest.json");
-                if old_manifest.exists() {
-                    if let Err(e) = fs::copy(&old_manifest, &manifest_path) {
-                        eprintln!("Failed to migrate manifest from {:?} to {:?}: {}", old_manifest, manifest_path, e);
-                    }
-                }
-            }
-        }
-    }
+    Ok(manifest::manifest_path()?.to_string_lossy().to_string())
+}
 
-    Ok(manifest_path.to_string_lossy().to_string())
+#[derive(Serialize)]
+struct RenameResult {
+    from: String,
+    to: String,
+    method: fsutil::MoveMethod,
+    // True when an existing destination file was replaced.
+    overwritten: bool,
 }
 
+// Rename or move a file. An existing destination is only replaced when
+// `overwrite` is set; otherwise the call fails and nothing is touched.
 #[tauri::command]
-fn rename_file(old_path: String, new_path: String) -> Result<(), String> {
-    fs::rename(old_path, new_path).map_err(|e| e.to_string())
+fn rename_file(old_path: String, new_path: String, overwrite: Option<bool>) -> Result<RenameResult, String> {
+    game::ensure_game_not_running()?;
+    let old_path = paths::resolve_writable(&old_path)?;
+    let new_path = paths::resolve_writable(&new_path)?;
+    let overwrite = overwrite.unwrap_or(false);
+
+    let overwritten = overwrite && new_path.exists() && !fsutil::is_same_file(&old_path, &new_path);
+    let method = fsutil::move_file_with(&old_path, &new_path, overwrite).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::AlreadyExists {
+            format!("Not renaming: {} already exists", new_path.display())
+        } else {
+            e.to_string()
+        }
+    })?;
+
+    Ok(RenameResult {
+        from: old_path.to_string_lossy().to_string(),
+        to: new_path.to_string_lossy().to_string(),
+        method,
+        overwritten,
+    })
 }
 
 #[tauri::command]
 fn scan_mods_folder(path: String) -> Result<Vec<String>, String> {
-    let mut files = Vec::new();
-    let dir_path = PathBuf::from(&path);
-    if !dir_path.exists() {
-        return Ok(files);
-    }
-    
-    let dir = fs::read_dir(dir_path).map_err(|e| e.to_string())?;
-    for entry in dir {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(ext) = path.extension() {
-                if ext.to_string_lossy().to_lowercase() == "zip" {
-                    if let Some(name) = path.file_name() {
-                        files.push(name.to_string_lossy().to_string());
-                    }
-                }
-            }
-        }
-    }
-    Ok(files)
+    reconcile::list_zip_files(&paths::resolve_allowed(&path)?)
 }
 
 #[tauri::command]
@@ -545,20 +485,9 @@ struct BackendConfig {
 
 #[tauri::command]
 fn get_patreon_config() -> BackendConfig {
-    // Resolve remote_mods_json_url with this priority:
-    // 1) Runtime env REMOTE_MODS_JSON_URL (for local testing/overrides)
-    // 2) Build-time REMOTE_MODS_JSON_URL via option_env! (for CI/Release builds)
-    // 3) Empty string (falls back to bundled public/mods.json)
-
-    let runtime_url = std::env::var("REMOTE_MODS_JSON_URL").ok().filter(|s| !s.is_empty());
-    let buildtime_url = option_env!("REMOTE_MODS_JSON_URL").map(|s| s.to_string()).filter(|s| !s.is_empty());
-
-    let remote_url = runtime_url
-        .or(buildtime_url)
-        .unwrap_or_default();
-
+    // Empty when not configured; see catalog::configured_url.
     BackendConfig {
-        remote_mods_json_url: remote_url,
+        remote_mods_json_url: catalog::configured_url().unwrap_or_default(),
     }
 }
 
@@ -567,6 +496,16 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .setup(|_app| {
+            // Import manifests from older install locations and schemas
+            // before anything reads the manifest.
+            if let Err(e) = migration::run() {
+                eprintln!("Manifest migration failed: {}", e);
+            }
+            // Finish deletions that were blocked by a file lock last session.
+            removal::process_pending_deletes();
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             detect_beamng_user_path,
             delete_old_mod,
@@ -580,7 +519,59 @@ pub fn run() {
             patreon_login,
             fetch_page_content,
             open_url_in_browser,
-            get_patreon_config
+            get_patreon_config,
+            catalog::load_catalog,
+            catalog_sources::get_catalog_sources,
+            catalog_sources::set_catalog_sources,
+            catalog_sources::add_local_catalog_source,
+            game::is_beamng_running,
+            trash::list_trash,
+            trash::restore_from_trash,
+            trash::purge_trash,
+            archive::list_archived_versions,
+            archive::rollback_mod,
+            cache::get_cache_stats,
+            cache::clear_cache,
+            migration::get_migration_log,
+            reconcile::reconcile_mods,
+            updates::check_updates,
+            install::install_mod,
+            github::resolve_github_releases,
+            changelog::get_changelog,
+            channels::channel_versions,
+            channels::get_mod_channels,
+            channels::set_mod_channel,
+            github::has_github_token,
+            github::set_github_token,
+            install::plan_install,
+            install::list_mod_versions,
+            settings::get_mods_folder,
+            settings::choose_mods_folder,
+            manifest::load_manifest,
+            manifest::upsert_manifest_entry,
+            manifest::remove_manifest_entry,
+            manifest::set_mod_pinned,
+            manifest::replace_manifest_entries,
+            modpack::export_modpack,
+            modpack::open_modpack,
+            modpack::apply_modpack,
+            mods_backup::create_mods_backup,
+            mods_backup::list_mods_backups,
+            mods_backup::delete_mods_backup,
+            mods_backup::preview_mods_backup,
+            mods_backup::restore_mods_backup,
+            game_cache::get_game_cache_info,
+            game_cache::clear_game_cache,
+            game_cache::set_clear_cache_after_update,
+            profiles::list_profiles,
+            profiles::save_profile,
+            profiles::capture_profile,
+            profiles::delete_profile,
+            profiles::switch_profile,
+            saves::list_save_backups,
+            saves::create_save_backup,
+            saves::restore_save_backup,
+            saves::prune_save_backups
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
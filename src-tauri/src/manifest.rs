@@ -0,0 +1,461 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{fsutil, paths};
+
+pub const MANIFEST_FILENAME: &str = "mod_manifest.json";
+
+// Previous manifests are kept next to the live one so a damaged file can
+// be recovered without losing the list of installed mods.
+const BACKUP_DIRNAME: &str = "manifest_backups";
+const MAX_BACKUPS: usize = 5;
+
+// Bump together with a new step in `migrate`.
+//   1: legacy `{ "<modId>": { "version", "filename" } }` map written by the frontend
+//   2: versioned document with typed entries
+pub const MANIFEST_SCHEMA_VERSION: u32 = 2;
+
+// Installs, rollbacks, pins and reconciles can run concurrently; serialize
+// every write so entries are not lost.
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ManifestEntry {
+    // Always equal to the key the entry is stored under.
+    #[serde(default)]
+    pub id: String,
+    pub version: String,
+    pub filename: String,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    // Unix timestamp (seconds)
+    #[serde(default)]
+    pub installed_at: Option<u64>,
+    // False for zips the installer only discovered in the mods folder.
+    #[serde(default)]
+    pub installed_by_installer: bool,
+    // Pinned mods are kept at their version: update checks skip them and
+    // dependencies never replace them.
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Manifest {
+    pub schema_version: u32,
+    pub mods: BTreeMap<String, ManifestEntry>,
+}
+
+impl Default for Manifest {
+    fn default() -> Self {
+        Manifest {
+            schema_version: MANIFEST_SCHEMA_VERSION,
+            mods: BTreeMap::new(),
+        }
+    }
+}
+
+// `recovered_from` when the manifest was damaged and no backup could be
+// read, so it was reset.
+const RECOVERED_EMPTY: &str = "empty";
+
+// What `load_manifest` hands to the frontend: the manifest plus the
+// backup it had to be recovered from, if the main file was unreadable.
+#[derive(Serialize, Clone, Debug)]
+pub struct LoadedManifest {
+    #[serde(flatten)]
+    pub manifest: Manifest,
+    pub recovered_from: Option<String>,
+}
+
+impl Manifest {
+    fn normalize(&mut self) {
+        for (id, entry) in self.mods.iter_mut() {
+            entry.id = id.clone();
+        }
+    }
+}
+
+pub fn manifest_path() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(MANIFEST_FILENAME))
+}
+
+fn parse_entries(entries: serde_json::Map<String, Value>) -> BTreeMap<String, ManifestEntry> {
+    entries
+        .into_iter()
+        .filter_map(|(id, value)| match serde_json::from_value::<ManifestEntry>(value) {
+            Ok(entry) => Some((id, entry)),
+            Err(e) => {
+                // One damaged entry should not cost the user every other one.
+                eprintln!("Dropping invalid manifest entry {}: {}", id, e);
+                None
+            }
+        })
+        .collect()
+}
+
+fn document_schema_version(document: &serde_json::Map<String, Value>) -> Result<u32, String> {
+    match document.get("schema_version") {
+        None => Ok(1),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| format!("Invalid manifest schema_version {}", v)),
+    }
+}
+
+// Schema version of a manifest document as stored, before migration.
+pub fn stored_schema_version(content: &str) -> Option<u32> {
+    match serde_json::from_str::<Value>(content).ok()? {
+        Value::Object(document) => document_schema_version(&document).ok(),
+        _ => None,
+    }
+}
+
+// Bring any manifest document we ever wrote up to the current schema.
+pub fn migrate(value: Value) -> Result<Manifest, String> {
+    let Value::Object(mut document) = value else {
+        return Err("Manifest is not a JSON object".to_string());
+    };
+
+    let schema_version = document_schema_version(&document)?;
+
+    if schema_version > MANIFEST_SCHEMA_VERSION {
+        return Err(format!(
+            "Manifest schema version {} is newer than this installer supports ({})",
+            schema_version, MANIFEST_SCHEMA_VERSION
+        ));
+    }
+
+    let mods = match schema_version {
+        // The legacy format is the entries map itself.
+        1 => parse_entries(document),
+        _ => match document.remove("mods") {
+            Some(Value::Object(entries)) => parse_entries(entries),
+            Some(_) => return Err("Manifest `mods` is not an object".to_string()),
+            None => BTreeMap::new(),
+        },
+    };
+
+    let mut manifest = Manifest {
+        schema_version: MANIFEST_SCHEMA_VERSION,
+        mods,
+    };
+    manifest.normalize();
+    Ok(manifest)
+}
+
+pub fn parse(content: &str) -> Result<Manifest, String> {
+    let value: Value = serde_json::from_str(content).map_err(|e| format!("Invalid manifest: {}", e))?;
+    migrate(value)
+}
+
+fn backup_dir(path: &Path) -> PathBuf {
+    path.parent().unwrap_or(Path::new(".")).join(BACKUP_DIRNAME)
+}
+
+// Backups are named `mod_manifest.<unix millis>.json`; newest first.
+fn list_backups(path: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(backup_dir(path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(u128, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stamp = name.strip_prefix("mod_manifest.")?.strip_suffix(".json")?.parse().ok()?;
+            Some((stamp, entry.path()))
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.0));
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+// Copy the current manifest into the backup rotation before it gets
+// replaced. Only a manifest that parses is worth keeping, and identical
+// consecutive copies are skipped so frequent saves do not push out older
+// distinct versions.
+fn rotate_backup(path: &Path) -> Result<(), String> {
+    let current = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.to_string()),
+    };
+    if parse(&current).is_err() {
+        return Ok(());
+    }
+
+    let backups = list_backups(path);
+    if let Some(newest) = backups.first() {
+        if fs::read_to_string(newest).ok().as_deref() == Some(current.as_str()) {
+            return Ok(());
+        }
+    }
+
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+    fsutil::write_atomic(&dir.join(format!("mod_manifest.{}.json", stamp)), current.as_bytes())
+        .map_err(|e| e.to_string())?;
+
+    for stale in backups.iter().skip(MAX_BACKUPS - 1) {
+        if let Err(e) = fs::remove_file(stale) {
+            eprintln!("Failed to remove old manifest backup {:?}: {}", stale, e);
+        }
+    }
+    Ok(())
+}
+
+// Load the manifest, falling back to the newest backup that parses when
+// the main file is damaged. The damaged file is kept aside as
+// `mod_manifest.json.corrupt` and replaced by the recovered copy, or by an
+// empty manifest when no backup parses either; `recovered_from` is then
+// `RECOVERED_EMPTY`.
+pub fn load_recovering(path: &Path) -> Result<LoadedManifest, String> {
+    let (error, damaged) = match fs::read_to_string(path) {
+        Ok(content) => match parse(&content) {
+            Ok(manifest) => return Ok(LoadedManifest { manifest, recovered_from: None }),
+            // Written by a newer installer, not damaged: leave it alone.
+            Err(e) if stored_schema_version(&content).is_some_and(|v| v > MANIFEST_SCHEMA_VERSION) => return Err(e),
+            Err(e) => (format!("{} ({})", e, path.display()), true),
+        },
+        // A missing file is an empty manifest, e.g. on first launch.
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            return Ok(LoadedManifest { manifest: Manifest::default(), recovered_from: None });
+        }
+        Err(e) => (e.to_string(), false),
+    };
+
+    let recovered = list_backups(path).into_iter().find_map(|backup| {
+        let manifest = fs::read_to_string(&backup).map_err(|e| e.to_string()).and_then(|c| parse(&c)).ok()?;
+        Some((manifest, backup.to_string_lossy().to_string()))
+    });
+    let (manifest, recovered_from) = match recovered {
+        Some(recovered) => recovered,
+        // A file that could not be read, e.g. because it is locked, may
+        // well be fine; an empty manifest must not replace it.
+        None if !damaged => return Err(error),
+        None => (Manifest::default(), RECOVERED_EMPTY.to_string()),
+    };
+
+    eprintln!("Manifest {} is unreadable ({}), recovered from {}", path.display(), error, recovered_from);
+    // The damaged file is the only record left of what it listed; never
+    // overwrite it without a copy.
+    let corrupt_path = path.with_extension("json.corrupt");
+    fs::rename(path, &corrupt_path).map_err(|e| format!("{}; failed to set it aside: {}", error, e))?;
+    // Not `save`: `update` holds the lock while loading.
+    write(path, &manifest)?;
+
+    Ok(LoadedManifest {
+        manifest,
+        recovered_from: Some(recovered_from),
+    })
+}
+
+pub fn load(path: &Path) -> Result<Manifest, String> {
+    load_recovering(path).map(|loaded| loaded.manifest)
+}
+
+fn write(path: &Path, manifest: &Manifest) -> Result<(), String> {
+    let mut manifest = manifest.clone();
+    manifest.schema_version = MANIFEST_SCHEMA_VERSION;
+    manifest.normalize();
+
+    if let Err(e) = rotate_backup(path) {
+        eprintln!("Failed to back up manifest before saving: {}", e);
+    }
+
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(path, content.as_bytes()).map_err(|e| e.to_string())
+}
+
+// Crash-safe write of the manifest, keeping the previous version in the
+// backup rotation. Use `update` to change entries of the live manifest.
+pub fn save(path: &Path, manifest: &Manifest) -> Result<(), String> {
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    write(path, manifest)
+}
+
+// Read-modify-write of the live manifest under the lock. Nothing is
+// written when `change` fails or leaves the manifest as it was.
+pub fn update<F>(change: F) -> Result<Manifest, String>
+where
+    F: FnOnce(&mut Manifest) -> Result<(), String>,
+{
+    let _guard = MANIFEST_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = manifest_path()?;
+    let mut manifest = load(&path)?;
+    let before = serde_json::to_value(&manifest).map_err(|e| e.to_string())?;
+    change(&mut manifest)?;
+    if serde_json::to_value(&manifest).map_err(|e| e.to_string())? != before {
+        write(&path, &manifest)?;
+    }
+    Ok(manifest)
+}
+
+#[tauri::command]
+pub fn load_manifest() -> Result<LoadedManifest, String> {
+    load_recovering(&manifest_path()?)
+}
+
+#[tauri::command]
+pub fn upsert_manifest_entry(mod_id: String, mut entry: ManifestEntry) -> Result<Manifest, String> {
+    if entry.installed_at.is_none() {
+        entry.installed_at = Some(fsutil::unix_now());
+    }
+    update(|manifest| {
+        manifest.mods.insert(mod_id, entry);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn remove_manifest_entry(mod_id: String) -> Result<Manifest, String> {
+    update(|manifest| {
+        manifest.mods.remove(&mod_id);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn set_mod_pinned(mod_id: String, pinned: bool) -> Result<Manifest, String> {
+    update(|manifest| {
+        let entry = manifest.mods.get_mut(&mod_id).ok_or_else(|| format!("{} is not installed", mod_id))?;
+        entry.pinned = pinned;
+        Ok(())
+    })
+}
+
+// Replace every entry at once, used after reconciling with the mods folder.
+#[tauri::command]
+pub fn replace_manifest_entries(mods: BTreeMap<String, ManifestEntry>) -> Result<Manifest, String> {
+    update(|manifest| {
+        manifest.mods = mods;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn manifest_with(mods: &[(&str, &str)]) -> Manifest {
+        let mut manifest = Manifest::default();
+        for (id, version) in mods {
+            manifest.mods.insert(
+                id.to_string(),
+                ManifestEntry {
+                    version: version.to_string(),
+                    filename: format!("{}_{}.zip", id, version),
+                    ..Default::default()
+                },
+            );
+        }
+        manifest
+    }
+
+    // Backups are named by the millisecond they were taken.
+    fn save_later(path: &Path, manifest: &Manifest) {
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        save(path, manifest).unwrap();
+    }
+
+    #[test]
+    fn migrates_v1_manifest() {
+        let manifest = parse(r#"{ "career": { "version": "2.6.1", "filename": "career_2.6.1.zip" } }"#).unwrap();
+
+        assert_eq!(manifest.schema_version, MANIFEST_SCHEMA_VERSION);
+        let entry = &manifest.mods["career"];
+        assert_eq!(entry.id, "career");
+        assert_eq!(entry.version, "2.6.1");
+        assert!(!entry.installed_by_installer);
+    }
+
+    #[test]
+    fn drops_invalid_entries() {
+        let manifest = parse(
+            r#"{ "schema_version": 2, "mods": {
+                "career": { "version": "2.6.1", "filename": "career.zip" },
+                "broken": { "version": 3 },
+                "traffic": "traffic.zip"
+            } }"#,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.mods.keys().collect::<Vec<_>>(), ["career"]);
+    }
+
+    #[test]
+    fn refuses_newer_schema() {
+        let err = parse(r#"{ "schema_version": 99, "mods": {} }"#).unwrap_err();
+        assert!(err.contains("newer"), "{}", err);
+    }
+
+    #[test]
+    fn rotates_backups() {
+        let dir = TempDir::new("manifest-rotate");
+        let path = dir.join(MANIFEST_FILENAME);
+
+        save(&path, &manifest_with(&[("career", "1.0")])).unwrap();
+        assert!(list_backups(&path).is_empty());
+
+        for minor in 1..=MAX_BACKUPS + 2 {
+            save_later(&path, &manifest_with(&[("career", &format!("1.{}", minor))]));
+        }
+        let backups = list_backups(&path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        // Newest first: the version replaced by the last save.
+        let newest = parse(&fs::read_to_string(&backups[0]).unwrap()).unwrap();
+        assert_eq!(newest.mods["career"].version, format!("1.{}", MAX_BACKUPS + 1));
+
+        // Saving the same content over and over keeps one copy of it.
+        let current = load(&path).unwrap();
+        save_later(&path, &current);
+        let newest = list_backups(&path)[0].clone();
+        save_later(&path, &current);
+        assert_eq!(list_backups(&path)[0], newest);
+    }
+
+    #[test]
+    fn recovers_from_newest_backup() {
+        let dir = TempDir::new("manifest-recover");
+        let path = dir.join(MANIFEST_FILENAME);
+        save(&path, &manifest_with(&[("career", "2.6.1")])).unwrap();
+        save_later(&path, &manifest_with(&[("career", "2.6.2")]));
+        fs::write(&path, "{ not json").unwrap();
+
+        let loaded = load_recovering(&path).unwrap();
+
+        assert_eq!(loaded.manifest.mods["career"].version, "2.6.1");
+        assert!(loaded.recovered_from.is_some_and(|from| from != RECOVERED_EMPTY));
+        assert_eq!(fs::read_to_string(path.with_extension("json.corrupt")).unwrap(), "{ not json");
+        assert_eq!(load(&path).unwrap().mods["career"].version, "2.6.1");
+    }
+
+    #[test]
+    fn falls_back_to_empty_without_backups() {
+        let dir = TempDir::new("manifest-empty");
+        let path = dir.join(MANIFEST_FILENAME);
+        fs::write(&path, "{ not json").unwrap();
+
+        let loaded = load_recovering(&path).unwrap();
+
+        assert!(loaded.manifest.mods.is_empty());
+        assert_eq!(loaded.recovered_from.as_deref(), Some(RECOVERED_EMPTY));
+        assert_eq!(fs::read_to_string(path.with_extension("json.corrupt")).unwrap(), "{ not json");
+        assert_eq!(load_recovering(&path).unwrap().recovered_from, None);
+    }
+}
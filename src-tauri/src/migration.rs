@@ -0,0 +1,360 @@
+use std::collections::btree_map::Entry;
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::{fsutil, manifest, paths};
+
+const MIGRATION_LOG_FILENAME: &str = "migrations.json";
+
+// Suffix given to a legacy manifest once its entries have been imported,
+// so it is neither picked up again nor mistaken for the live manifest.
+const MIGRATED_SUFFIX: &str = ".migrated";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationKind {
+    // A manifest found in a location used by older versions was imported.
+    LegacyLocation,
+    // The live manifest was rewritten in the current schema.
+    Schema,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct MigrationRecord {
+    pub kind: MigrationKind,
+    // Unix timestamp (seconds)
+    pub at: u64,
+    pub source: Option<String>,
+    // SHA-256 of the legacy file, used to recognise a file we already
+    // imported when it could not be renamed (e.g. under Program Files).
+    pub source_sha256: Option<String>,
+    pub from_schema: u32,
+    pub to_schema: u32,
+    // Entries taken over from the legacy manifest.
+    pub imported: usize,
+    // Entries already present in the live manifest, which always wins.
+    pub skipped: usize,
+    // Whether the legacy file was renamed with the `.migrated` suffix.
+    pub legacy_marked: bool,
+    pub error: Option<String>,
+}
+
+// Bundle identifier from tauri.conf.json; Tauri names its default app
+// data folders after it.
+const APP_IDENTIFIER: &str = "com.SH0UF.RLS-installer";
+
+// Where earlier installer versions may have kept their manifest:
+//   - next to the executable (Program Files installs, before the per-user
+//     data directory existed)
+//   - Tauri's default app data folders, %APPDATA% and %LOCALAPPDATA%
+//     named after the bundle identifier
+//   - %APPDATA%\RLS Installer and %LOCALAPPDATA%\rls-installer
+// The working directory is deliberately not searched; it can be any folder
+// the app was started from.
+pub fn legacy_locations() -> Vec<PathBuf> {
+    let exe_dir = std::env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let env_dir = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    legacy_locations_in(exe_dir, env_dir("APPDATA"), env_dir("LOCALAPPDATA"))
+}
+
+fn legacy_locations_in(exe_dir: Option<PathBuf>, app_data: Option<PathBuf>, local_app_data: Option<PathBuf>) -> Vec<PathBuf> {
+    let per_user = [
+        (app_data.as_ref(), APP_IDENTIFIER),
+        (app_data.as_ref(), "RLS Installer"),
+        (local_app_data.as_ref(), APP_IDENTIFIER),
+        (local_app_data.as_ref(), "rls-installer"),
+    ];
+    let mut dirs: Vec<PathBuf> = exe_dir.into_iter().collect();
+    for dir in per_user.iter().filter_map(|(base, name)| base.map(|base| base.join(name))) {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn log_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(MIGRATION_LOG_FILENAME)
+}
+
+pub fn read_log(data_dir: &Path) -> Vec<MigrationRecord> {
+    fs::read_to_string(log_path(data_dir))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn append_log(data_dir: &Path, records: &[MigrationRecord]) -> Result<(), String> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut log = read_log(data_dir);
+    log.extend_from_slice(records);
+    let content = serde_json::to_string_pretty(&log).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&log_path(data_dir), content.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn already_imported(log: &[MigrationRecord], source: &Path, sha256: &str) -> bool {
+    let source = source.to_string_lossy();
+    log.iter().any(|record| {
+        record.kind == MigrationKind::LegacyLocation
+            && record.error.is_none()
+            && record.source.as_deref() == Some(source.as_ref())
+            && record.source_sha256.as_deref() == Some(sha256)
+    })
+}
+
+fn import_legacy(legacy_path: &Path, live_path: &Path) -> MigrationRecord {
+    let mut record = MigrationRecord {
+        kind: MigrationKind::LegacyLocation,
+        at: fsutil::unix_now(),
+        source: Some(legacy_path.to_string_lossy().to_string()),
+        source_sha256: fsutil::sha256_file(legacy_path).ok(),
+        from_schema: 0,
+        to_schema: manifest::MANIFEST_SCHEMA_VERSION,
+        imported: 0,
+        skipped: 0,
+        legacy_marked: false,
+        error: None,
+    };
+
+    let result = fs::read_to_string(legacy_path).map_err(|e| e.to_string()).and_then(|content| {
+        record.from_schema = manifest::stored_schema_version(&content).unwrap_or(0);
+        let legacy = manifest::parse(&content)?;
+        let mut live = manifest::load(live_path)?;
+        for (id, entry) in legacy.mods {
+            match live.mods.entry(id) {
+                Entry::Occupied(_) => record.skipped += 1,
+                Entry::Vacant(slot) => {
+                    slot.insert(entry);
+                    record.imported += 1;
+                }
+            }
+        }
+        manifest::save(live_path, &live)
+    });
+    if let Err(e) = result {
+        record.error = Some(e);
+        return record;
+    }
+
+    let mut marked = legacy_path.as_os_str().to_owned();
+    marked.push(MIGRATED_SUFFIX);
+    match fsutil::move_file(legacy_path, Path::new(&marked)) {
+        Ok(_) => record.legacy_marked = true,
+        Err(e) => eprintln!("Could not mark {:?} as migrated, relying on the migration log: {}", legacy_path, e),
+    }
+    record
+}
+
+// Import manifests from `legacy_dirs` into the manifest in `data_dir`, then
+// bring the live manifest up to the current schema. Safe to run on every
+// launch: imported files are renamed or remembered by hash in the log.
+pub fn migrate_manifests(data_dir: &Path, legacy_dirs: &[PathBuf]) -> Vec<MigrationRecord> {
+    let live_path = data_dir.join(manifest::MANIFEST_FILENAME);
+    let log = read_log(data_dir);
+    let mut records = Vec::new();
+
+    for dir in legacy_dirs {
+        let legacy_path = dir.join(manifest::MANIFEST_FILENAME);
+        if !legacy_path.is_file() || legacy_path == live_path {
+            continue;
+        }
+        match fsutil::sha256_file(&legacy_path) {
+            Ok(hash) if already_imported(&log, &legacy_path, &hash) => continue,
+            Err(e) => eprintln!("Failed to read legacy manifest {:?}: {}", legacy_path, e),
+            _ => {}
+        }
+        records.push(import_legacy(&legacy_path, &live_path));
+    }
+
+    if let Ok(content) = fs::read_to_string(&live_path) {
+        let stored = manifest::stored_schema_version(&content);
+        if let Some(from_schema) = stored.filter(|v| *v < manifest::MANIFEST_SCHEMA_VERSION) {
+            let result = manifest::parse(&content).and_then(|m| manifest::save(&live_path, &m));
+            records.push(MigrationRecord {
+                kind: MigrationKind::Schema,
+                at: fsutil::unix_now(),
+                source: None,
+                source_sha256: None,
+                from_schema,
+                to_schema: manifest::MANIFEST_SCHEMA_VERSION,
+                imported: 0,
+                skipped: 0,
+                legacy_marked: false,
+                error: result.err(),
+            });
+        }
+    }
+
+    if let Err(e) = append_log(data_dir, &records) {
+        eprintln!("Failed to write migration log: {}", e);
+    }
+    records
+}
+
+// Run every pending migration against the real data directory.
+pub fn run() -> Result<Vec<MigrationRecord>, String> {
+    Ok(migrate_manifests(&paths::data_dir()?, &legacy_locations()))
+}
+
+#[tauri::command]
+pub fn get_migration_log() -> Result<Vec<MigrationRecord>, String> {
+    Ok(read_log(&paths::data_dir()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn setup(name: &str, legacy: &str) -> (TempDir, PathBuf, PathBuf) {
+        let root = TempDir::new(name);
+        let data_dir = root.join("data");
+        let legacy_dir = root.join("legacy");
+        fs::create_dir_all(&data_dir).unwrap();
+        fs::create_dir_all(&legacy_dir).unwrap();
+        fs::write(legacy_dir.join(manifest::MANIFEST_FILENAME), legacy).unwrap();
+        (root, data_dir, legacy_dir)
+    }
+
+    fn live(data_dir: &Path) -> manifest::Manifest {
+        manifest::load(&data_dir.join(manifest::MANIFEST_FILENAME)).unwrap()
+    }
+
+    const LEGACY: &str = r#"{
+        "career": { "version": "2.6.1", "filename": "career_2.6.1.zip" },
+        "traffic": { "version": "1.0", "filename": "traffic.zip" }
+    }"#;
+
+    #[test]
+    fn imports_legacy_manifest() {
+        let (_root, data_dir, legacy_dir) = setup("import", LEGACY);
+
+        let records = migrate_manifests(&data_dir, std::slice::from_ref(&legacy_dir));
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].kind, MigrationKind::LegacyLocation);
+        assert_eq!(records[0].imported, 2);
+        assert_eq!(records[0].from_schema, 1);
+        assert!(records[0].error.is_none());
+        let live = live(&data_dir);
+        assert_eq!(live.mods["career"].version, "2.6.1");
+        assert_eq!(live.mods["traffic"].filename, "traffic.zip");
+        assert_eq!(read_log(&data_dir).len(), 1);
+    }
+
+    #[test]
+    fn keeps_entries_already_in_live_manifest() {
+        let (_root, data_dir, legacy_dir) = setup("skip", LEGACY);
+        let current = r#"{ "schema_version": 2, "mods": { "career": { "version": "2.6.2", "filename": "career_2.6.2.zip" } } }"#;
+        fs::write(data_dir.join(manifest::MANIFEST_FILENAME), current).unwrap();
+
+        let records = migrate_manifests(&data_dir, &[legacy_dir]);
+
+        assert_eq!(records[0].imported, 1);
+        assert_eq!(records[0].skipped, 1);
+        let live = live(&data_dir);
+        assert_eq!(live.mods["career"].version, "2.6.2");
+        assert!(live.mods.contains_key("traffic"));
+    }
+
+    #[test]
+    fn marks_imported_file_as_migrated() {
+        let (_root, data_dir, legacy_dir) = setup("rename", LEGACY);
+
+        let records = migrate_manifests(&data_dir, std::slice::from_ref(&legacy_dir));
+
+        assert!(records[0].legacy_marked);
+        assert!(!legacy_dir.join(manifest::MANIFEST_FILENAME).exists());
+        assert!(legacy_dir.join(format!("{}{}", manifest::MANIFEST_FILENAME, MIGRATED_SUFFIX)).is_file());
+        assert!(migrate_manifests(&data_dir, &[legacy_dir]).is_empty());
+    }
+
+    #[test]
+    fn log_skips_files_imported_before() {
+        let (_root, data_dir, legacy_dir) = setup("log", LEGACY);
+        let legacy_path = legacy_dir.join(manifest::MANIFEST_FILENAME);
+        migrate_manifests(&data_dir, std::slice::from_ref(&legacy_dir));
+
+        // Same content back in place, as when the rename was not allowed.
+        fs::write(&legacy_path, LEGACY).unwrap();
+        assert!(migrate_manifests(&data_dir, std::slice::from_ref(&legacy_dir)).is_empty());
+        assert_eq!(read_log(&data_dir).len(), 1);
+
+        // A different file at the same place is imported again.
+        fs::write(&legacy_path, r#"{ "other": { "version": "1", "filename": "other.zip" } }"#).unwrap();
+        let records = migrate_manifests(&data_dir, &[legacy_dir]);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].imported, 1);
+        assert_eq!(read_log(&data_dir).len(), 2);
+    }
+
+    #[test]
+    fn lists_exe_dir_and_old_per_user_dirs() {
+        let dirs = legacy_locations_in(
+            Some(PathBuf::from("C:/Program Files/RLS Installer")),
+            Some(PathBuf::from("C:/Users/me/AppData/Roaming")),
+            Some(PathBuf::from("C:/Users/me/AppData/Local")),
+        );
+
+        assert_eq!(
+            dirs,
+            [
+                PathBuf::from("C:/Program Files/RLS Installer"),
+                PathBuf::from("C:/Users/me/AppData/Roaming").join(APP_IDENTIFIER),
+                PathBuf::from("C:/Users/me/AppData/Roaming/RLS Installer"),
+                PathBuf::from("C:/Users/me/AppData/Local").join(APP_IDENTIFIER),
+                PathBuf::from("C:/Users/me/AppData/Local/rls-installer"),
+            ]
+        );
+        assert!(legacy_locations_in(None, None, None).is_empty());
+    }
+
+    #[test]
+    fn imports_from_every_legacy_location() {
+        let root = TempDir::new("locations");
+        let (exe_dir, roaming, local) = (root.join("exe"), root.join("Roaming"), root.join("Local"));
+        let data_dir = local.join("RLS Installer");
+        let legacy = [
+            (exe_dir.clone(), "exe_mod"),
+            (roaming.join("RLS Installer"), "roaming_mod"),
+            (local.join(APP_IDENTIFIER), "tauri_mod"),
+            (local.join("rls-installer"), "dashed_mod"),
+        ];
+        fs::create_dir_all(&data_dir).unwrap();
+        for (dir, id) in &legacy {
+            fs::create_dir_all(dir).unwrap();
+            let content = format!(r#"{{ "{}": {{ "version": "1.0", "filename": "{}.zip" }} }}"#, id, id);
+            fs::write(dir.join(manifest::MANIFEST_FILENAME), content).unwrap();
+        }
+
+        let records = migrate_manifests(&data_dir, &legacy_locations_in(Some(exe_dir), Some(roaming), Some(local)));
+
+        assert_eq!(records.len(), legacy.len());
+        assert!(records.iter().all(|r| r.error.is_none() && r.imported == 1));
+        let live = live(&data_dir);
+        for (_, id) in &legacy {
+            assert!(live.mods.contains_key(*id), "{} was not imported", id);
+        }
+    }
+
+    #[test]
+    fn upgrades_live_manifest_schema() {
+        let root = TempDir::new("schema");
+        let live_path = root.join(manifest::MANIFEST_FILENAME);
+        fs::write(&live_path, LEGACY).unwrap();
+
+        let records = migrate_manifests(&root, &[]);
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].kind, MigrationKind::Schema);
+        assert_eq!((records[0].from_schema, records[0].to_schema), (1, manifest::MANIFEST_SCHEMA_VERSION));
+        let content = fs::read_to_string(&live_path).unwrap();
+        assert_eq!(manifest::stored_schema_version(&content), Some(manifest::MANIFEST_SCHEMA_VERSION));
+        assert_eq!(live(&root).mods.len(), 2);
+        // Nothing left to do on the next launch.
+        assert!(migrate_manifests(&root, &[]).is_empty());
+    }
+}
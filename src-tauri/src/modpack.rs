@@ -0,0 +1,384 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use serde::{Deserialize, Serialize};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::catalog::{self, ModConfig};
+use crate::channels::{self, Channel};
+use crate::install::{self, InstallableVersion};
+use crate::manifest::{self, Manifest};
+use crate::removal::{self, FileOwner, RemovalMode};
+use crate::{fsutil, game, profiles, reconcile, settings};
+
+// Written into every modpack so other JSON files are rejected on import.
+const MODPACK_FORMAT: &str = "rls-modpack";
+const MODPACK_FORMAT_VERSION: u32 = 1;
+
+// A shareable description of an installed setup: catalog mods by id and
+// version, plus zips the catalog does not know, which have to be copied
+// by hand.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Modpack {
+    pub format: String,
+    pub format_version: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<u64>,
+    #[serde(default)]
+    pub mods: Vec<ModpackMod>,
+    #[serde(default)]
+    pub unmanaged: Vec<UnmanagedFile>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModpackMod {
+    pub id: String,
+    pub version: String,
+    #[serde(default)]
+    pub channel: Channel,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct UnmanagedFile {
+    pub filename: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModpackChange {
+    pub mod_id: String,
+    pub name: String,
+    pub installed_version: Option<String>,
+    pub version: String,
+}
+
+// What importing a modpack would change on this machine.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ModpackPlan {
+    // Missing, or installed in another version or build.
+    pub install: Vec<ModpackChange>,
+    // Catalog mods installed here that the modpack does not contain.
+    pub remove: Vec<ModpackChange>,
+    pub unchanged: Vec<String>,
+    // Modpack mods that are in none of the enabled catalogs.
+    pub unknown: Vec<String>,
+    // Unmanaged zips of the modpack that are not in the mods folder.
+    pub missing_files: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModpackImport {
+    pub path: String,
+    pub modpack: Modpack,
+    pub plan: ModpackPlan,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModpackFailure {
+    pub mod_id: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ModpackResult {
+    pub installed: Vec<String>,
+    pub removed: Vec<String>,
+    // Includes mods whose download differs from the file in the modpack.
+    pub failed: Vec<ModpackFailure>,
+    // Modpack mods in none of the enabled catalogs; left alone entirely.
+    pub unknown: Vec<String>,
+    pub manifest: Manifest,
+}
+
+pub fn build(name: Option<String>, installed: &Manifest, catalog: &[ModConfig], folder_files: &[String]) -> Modpack {
+    let known: BTreeSet<&str> = catalog.iter().map(|config| config.id.as_str()).collect();
+    let preferences = settings::load().mod_channels;
+
+    let mods = installed
+        .mods
+        .values()
+        .filter(|entry| known.contains(entry.id.as_str()))
+        .map(|entry| ModpackMod {
+            id: entry.id.clone(),
+            version: entry.version.clone(),
+            channel: preferences.get(&entry.id).copied().unwrap_or_default(),
+            pinned: entry.pinned,
+            sha256: entry.sha256.clone(),
+        })
+        .collect();
+
+    // Zips the catalog cannot install: manifest entries of unknown mods
+    // and files the manifest does not track at all.
+    let mut unmanaged: BTreeMap<String, UnmanagedFile> = installed
+        .mods
+        .values()
+        .filter(|entry| !known.contains(entry.id.as_str()))
+        .map(|entry| {
+            let file = UnmanagedFile {
+                filename: entry.filename.clone(),
+                sha256: entry.sha256.clone(),
+                size: entry.size,
+            };
+            (entry.filename.clone(), file)
+        })
+        .collect();
+    let tracked: BTreeSet<&str> = installed.mods.values().map(|entry| entry.filename.as_str()).collect();
+    for file in folder_files.iter().filter(|file| !tracked.contains(file.as_str())) {
+        unmanaged.entry(file.clone()).or_insert_with(|| UnmanagedFile {
+            filename: file.clone(),
+            sha256: None,
+            size: None,
+        });
+    }
+
+    Modpack {
+        format: MODPACK_FORMAT.to_string(),
+        format_version: MODPACK_FORMAT_VERSION,
+        name,
+        created_at: Some(fsutil::unix_now()),
+        mods,
+        unmanaged: unmanaged.into_values().collect(),
+    }
+}
+
+pub fn parse(content: &str) -> Result<Modpack, String> {
+    let modpack: Modpack = serde_json::from_str(content).map_err(|e| format!("Invalid modpack: {}", e))?;
+    if modpack.format != MODPACK_FORMAT {
+        return Err(format!("Not a modpack file (format {:?})", modpack.format));
+    }
+    if modpack.format_version > MODPACK_FORMAT_VERSION {
+        return Err(format!(
+            "Modpack format version {} is newer than this installer supports ({})",
+            modpack.format_version, MODPACK_FORMAT_VERSION
+        ));
+    }
+    Ok(modpack)
+}
+
+// Compare a modpack with what is installed. A mod is unchanged when the
+// version matches and, if both sides recorded one, so does the hash.
+pub fn plan(modpack: &Modpack, installed: &Manifest, catalog: &[ModConfig], folder_files: &[String]) -> ModpackPlan {
+    let configs: BTreeMap<&str, &ModConfig> = catalog.iter().map(|config| (config.id.as_str(), config)).collect();
+    let mut plan = ModpackPlan::default();
+
+    for wanted in &modpack.mods {
+        let Some(config) = configs.get(wanted.id.as_str()) else {
+            plan.unknown.push(wanted.id.clone());
+            continue;
+        };
+        let current = installed.mods.get(&wanted.id);
+        let same_build = current.is_some_and(|entry| {
+            entry.version == wanted.version
+                && match (&entry.sha256, &wanted.sha256) {
+                    (Some(have), Some(want)) => have.eq_ignore_ascii_case(want),
+                    _ => true,
+                }
+        });
+        if same_build {
+            plan.unchanged.push(wanted.id.clone());
+        } else {
+            plan.install.push(ModpackChange {
+                mod_id: wanted.id.clone(),
+                name: config.name.clone(),
+                installed_version: current.map(|entry| entry.version.clone()),
+                version: wanted.version.clone(),
+            });
+        }
+    }
+
+    let wanted: BTreeSet<&str> = modpack.mods.iter().map(|m| m.id.as_str()).collect();
+    for entry in installed.mods.values().filter(|entry| !wanted.contains(entry.id.as_str())) {
+        if let Some(config) = configs.get(entry.id.as_str()) {
+            plan.remove.push(ModpackChange {
+                mod_id: entry.id.clone(),
+                name: config.name.clone(),
+                installed_version: Some(entry.version.clone()),
+                version: entry.version.clone(),
+            });
+        }
+    }
+
+    plan.missing_files = modpack
+        .unmanaged
+        .iter()
+        .filter(|file| !folder_files.contains(&file.filename))
+        .map(|file| file.filename.clone())
+        .collect();
+    plan
+}
+
+fn current_state() -> Result<(Manifest, Vec<String>), String> {
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let folder_files = reconcile::list_zip_files(&settings::mods_folder()?)?;
+    Ok((installed, folder_files))
+}
+
+// Save the current setup as a modpack file chosen in a native dialog.
+// Returns the path, or None when cancelled.
+#[tauri::command]
+pub async fn export_modpack<R: tauri::Runtime>(app: tauri::AppHandle<R>, name: Option<String>) -> Result<Option<String>, String> {
+    let (installed, folder_files) = current_state()?;
+    let modpack = build(name, &installed, &catalog::loaded(), &folder_files);
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Export modpack")
+        .add_filter("Modpack", &["json"])
+        .set_file_name("modpack.json")
+        .save_file(move |file| {
+            let _ = tx.send(file);
+        });
+    let Some(file) = rx.await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let path = file.into_path().map_err(|e| e.to_string())?;
+
+    let content = serde_json::to_string_pretty(&modpack).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&path, content.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+// Read a modpack picked in a native dialog and work out what applying it
+// would change. Nothing is touched; returns None when cancelled.
+#[tauri::command]
+pub async fn open_modpack<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> Result<Option<ModpackImport>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .set_title("Import modpack")
+        .add_filter("Modpack", &["json"])
+        .pick_file(move |file| {
+            let _ = tx.send(file);
+        });
+    let Some(file) = rx.await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let path = file.into_path().map_err(|e| e.to_string())?;
+
+    let modpack = parse(&fs::read_to_string(&path).map_err(|e| e.to_string())?)?;
+    let (installed, folder_files) = current_state()?;
+    let plan = plan(&modpack, &installed, &catalog::loaded(), &folder_files);
+    Ok(Some(ModpackImport {
+        path: path.to_string_lossy().to_string(),
+        modpack,
+        plan,
+    }))
+}
+
+fn find_version<'a>(versions: &'a [InstallableVersion], wanted: &str) -> Option<&'a InstallableVersion> {
+    versions.iter().find(|v| v.version == wanted)
+}
+
+// Make this machine match a modpack: each catalog mod's channel and pin
+// are taken over and the listed version is installed, failing when its
+// download does not have the modpack's SHA-256. With `remove_extra`,
+// catalog mods the modpack does not contain go to the trash, from the mods
+// folder or the inactive store. Mods that fail, and mods no catalog
+// knows, are reported and the rest carry on.
+#[tauri::command]
+pub async fn apply_modpack(window: tauri::Window, modpack: Modpack, remove_extra: bool) -> Result<ModpackResult, String> {
+    game::ensure_game_not_running()?;
+    let catalog = catalog::loaded();
+    let (installed, folder_files) = current_state()?;
+    let plan = plan(&modpack, &installed, &catalog, &folder_files);
+
+    let mut result = ModpackResult {
+        installed: Vec::new(),
+        removed: Vec::new(),
+        failed: Vec::new(),
+        unknown: plan.unknown.clone(),
+        manifest: installed.clone(),
+    };
+
+    // No channel preference for ids no catalog knows.
+    for wanted in modpack.mods.iter().filter(|m| !plan.unknown.contains(&m.id)) {
+        if let Err(e) = channels::set_mod_channel(wanted.id.clone(), wanted.channel) {
+            eprintln!("Failed to set the release channel of {}: {}", wanted.id, e);
+        }
+    }
+
+    for change in &plan.install {
+        let Some(wanted) = modpack.mods.iter().find(|m| m.id == change.mod_id) else {
+            continue;
+        };
+        let attempt = async {
+            let config = catalog
+                .iter()
+                .find(|c| c.id == wanted.id)
+                .ok_or_else(|| format!("{} is not in the catalog", wanted.id))?;
+            let versions = install::installable_versions(config).await?;
+            let build = find_version(&versions, &wanted.version)
+                .ok_or_else(|| format!("Version {} of {} is no longer available", wanted.version, config.name))?;
+            install::install_mod(
+                window.clone(),
+                wanted.id.clone(),
+                Some(build.version.clone()),
+                Some(build.download_url.clone()),
+                Some(wanted.pinned),
+                wanted.sha256.clone(),
+            )
+            .await
+        };
+        match attempt.await {
+            Ok(_) => result.installed.push(wanted.id.clone()),
+            Err(error) => result.failed.push(ModpackFailure {
+                mod_id: wanted.id.clone(),
+                error,
+            }),
+        }
+    }
+
+    // Mods already at the right version only need their pin brought over.
+    for id in &plan.unchanged {
+        let pinned = modpack.mods.iter().any(|m| m.id == *id && m.pinned);
+        if installed.mods.get(id).is_some_and(|entry| entry.pinned != pinned) {
+            manifest::set_mod_pinned(id.clone(), pinned)?;
+        }
+    }
+
+    if remove_extra {
+        let mods_folder = settings::mods_folder()?;
+        let inactive_dir = profiles::inactive_dir()?;
+        for change in &plan.remove {
+            let Some(entry) = installed.mods.get(&change.mod_id) else {
+                continue;
+            };
+            let owner = FileOwner {
+                mod_id: Some(entry.id.clone()),
+                version: Some(entry.version.clone()),
+            };
+            // A mod disabled by a profile lives in the inactive store.
+            let mut path = mods_folder.join(&entry.filename);
+            let parked = inactive_dir.join(&entry.filename);
+            if !path.exists() && parked.exists() {
+                path = parked;
+            }
+            let trashed = if path.exists() {
+                let path = path.to_string_lossy().to_string();
+                install::ensure_removed(removal::delete_file(&path, RemovalMode::Trash, owner, false).await)
+            } else {
+                Ok(())
+            };
+            let removed = trashed.and_then(|_| manifest::remove_manifest_entry(entry.id.clone()));
+            match removed {
+                Ok(_) => result.removed.push(entry.id.clone()),
+                Err(error) => result.failed.push(ModpackFailure {
+                    mod_id: entry.id.clone(),
+                    error,
+                }),
+            }
+        }
+    }
+
+    result.manifest = manifest::load(&manifest::manifest_path()?)?;
+    Ok(result)
+}
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::backups::{self, BackupMetadata, BackupStore};
+use crate::removal::{self, DeleteOutcome, FileOwner, RemovalMode};
+use crate::{download, fsutil, game, manifest, profiles, reconcile, settings};
+
+const STORE: BackupStore = BackupStore {
+    dirname: "mods_backups",
+    prefix: "mods",
+    noun: "mods backup",
+};
+// Folders inside the backup zip holding the mods folder's files and the
+// zips a profile parked in the inactive store.
+const MODS_PREFIX: &str = "mods/";
+const INACTIVE_PREFIX: &str = "inactive_mods/";
+const MOD_DB_FILENAME: &str = "db.json";
+
+// Safety backups taken before restores that are kept; backups made by the
+// user are never pruned.
+const MAX_SAFETY_BACKUPS: usize = 3;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BackedUpFile {
+    pub filename: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModsBackup {
+    pub id: String,
+    // Unix timestamp (seconds)
+    pub created_at: u64,
+    pub label: Option<String>,
+    // Mod zips in the mods folder at the time of the backup.
+    pub files: Vec<BackedUpFile>,
+    // Zips disabled by a profile; the manifest can still list them.
+    #[serde(default)]
+    pub inactive: Vec<BackedUpFile>,
+    pub has_db: bool,
+    pub has_manifest: bool,
+    // Taken by the installer before a restore rather than by the user.
+    #[serde(default)]
+    pub automatic: bool,
+    // Size of the backup zip on disk.
+    #[serde(default)]
+    pub size: u64,
+}
+
+// What restoring a backup would do to the mods folder.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct RestorePreview {
+    pub backup_id: String,
+    // In the backup but not in the mods folder.
+    pub added: Vec<String>,
+    // In the mods folder but not in the backup; moved to the trash.
+    pub removed: Vec<String>,
+    // In both with different contents; the current file goes to the trash.
+    pub overwritten: Vec<String>,
+    pub unchanged: Vec<String>,
+    // Missing from or different in the inactive store; written back there.
+    // Other zips in the store are left alone.
+    pub inactive: Vec<String>,
+    pub restores_db: bool,
+    pub restores_manifest: bool,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RestoreFailure {
+    pub filename: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RestoreReport {
+    pub preview: RestorePreview,
+    // Backup of the mods folder as it was before restoring.
+    pub safety_backup: ModsBackup,
+    pub failed: Vec<RestoreFailure>,
+}
+
+impl BackupMetadata for ModsBackup {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    fn set_size(&mut self, size: u64) {
+        self.size = size;
+    }
+
+    // Restoring writes each file under its listed name; only plain file
+    // names qualify.
+    fn validate(&self) -> Result<(), String> {
+        match self.files.iter().chain(&self.inactive).find(|f| download::safe_filename(&f.filename) != Some(f.filename.as_str())) {
+            Some(file) => Err(format!("Mods backup {} lists an invalid file name {:?}", self.id, file.filename)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn add_files(zip: &mut ZipWriter<File>, prefix: &str, folder: &Path, files: &[BackedUpFile]) -> Result<(), String> {
+    // Mod zips are already compressed; storing them keeps backups fast.
+    let stored = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Stored)
+        .large_file(true);
+    for backed_up in files {
+        zip.start_file(format!("{}{}", prefix, backed_up.filename), stored)
+            .map_err(|e| e.to_string())?;
+        let mut source = File::open(folder.join(&backed_up.filename)).map_err(|e| e.to_string())?;
+        io::copy(&mut source, zip).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn write_backup(zip: &mut ZipWriter<File>, mods_folder: &Path, metadata: &ModsBackup) -> Result<(), String> {
+    let deflated = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    add_files(zip, MODS_PREFIX, mods_folder, &metadata.files)?;
+    add_files(zip, INACTIVE_PREFIX, &profiles::inactive_dir()?, &metadata.inactive)?;
+    if metadata.has_db {
+        zip.start_file(format!("{}{}", MODS_PREFIX, MOD_DB_FILENAME), deflated)
+            .map_err(|e| e.to_string())?;
+        zip.write_all(&fs::read(mods_folder.join(MOD_DB_FILENAME)).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+    }
+    if metadata.has_manifest {
+        zip.start_file(manifest::MANIFEST_FILENAME, deflated).map_err(|e| e.to_string())?;
+        zip.write_all(&fs::read(manifest::manifest_path()?).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+fn differs(path: &Path, file: &BackedUpFile) -> bool {
+    let same_size = fs::metadata(path).is_ok_and(|m| m.len() == file.size);
+    !(same_size && fsutil::sha256_file(path).is_ok_and(|hash| hash.eq_ignore_ascii_case(&file.sha256)))
+}
+
+// Compare a backup with the zips currently in the mods folder. Files of
+// the same size are compared by hash.
+pub fn preview(backup: &ModsBackup, mods_folder: &Path) -> Result<RestorePreview, String> {
+    let current = reconcile::list_zip_files(mods_folder)?;
+    let backed_up: BTreeMap<&str, &BackedUpFile> = backup.files.iter().map(|f| (f.filename.as_str(), f)).collect();
+    let mut preview = RestorePreview {
+        backup_id: backup.id.clone(),
+        restores_db: backup.has_db,
+        restores_manifest: backup.has_manifest,
+        ..Default::default()
+    };
+
+    for file in &backup.files {
+        let path = mods_folder.join(&file.filename);
+        if !current.contains(&file.filename) {
+            preview.added.push(file.filename.clone());
+            continue;
+        }
+        if differs(&path, file) {
+            preview.overwritten.push(file.filename.clone());
+        } else {
+            preview.unchanged.push(file.filename.clone());
+        }
+    }
+    preview.removed = current.into_iter().filter(|f| !backed_up.contains_key(f.as_str())).collect();
+
+    let inactive_dir = profiles::inactive_dir()?;
+    preview.inactive = backup
+        .inactive
+        .iter()
+        .filter(|file| differs(&inactive_dir.join(&file.filename), file))
+        .map(|file| file.filename.clone())
+        .collect();
+    Ok(preview)
+}
+
+async fn trash(mods_folder: &Path, filename: &str) -> DeleteOutcome {
+    let path = mods_folder.join(filename).to_string_lossy().to_string();
+    let owner = FileOwner {
+        mod_id: None,
+        version: None,
+    };
+    removal::delete_file(&path, RemovalMode::Trash, owner, false).await
+}
+
+fn extract_file(archive: &mut ZipArchive<File>, name: &str, target: &Path) -> Result<(), String> {
+    let mut entry = archive.by_name(name).map_err(|e| e.to_string())?;
+    let partial = target.with_extension("restore-partial");
+    let result = File::create(&partial)
+        .and_then(|mut file| io::copy(&mut entry, &mut file).and_then(|_| file.sync_all()))
+        .and_then(|_| fs::rename(&partial, target));
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result.map_err(|e| e.to_string())
+}
+
+fn backed_up_files(folder: &Path, filenames: Vec<String>) -> Result<Vec<BackedUpFile>, String> {
+    filenames
+        .into_iter()
+        .map(|filename| {
+            let path = folder.join(&filename);
+            Ok(BackedUpFile {
+                size: fs::metadata(&path).map_err(|e| e.to_string())?.len(),
+                sha256: fsutil::sha256_file(&path).map_err(|e| e.to_string())?,
+                filename,
+            })
+        })
+        .collect()
+}
+
+// Snapshot every mod zip in the mods folder and the inactive store,
+// BeamNG's db.json and the installer manifest into one zip in the data
+// folder.
+pub fn create_backup(label: Option<String>, automatic: bool) -> Result<ModsBackup, String> {
+    let mods_folder = settings::mods_folder()?;
+    let files = backed_up_files(&mods_folder, reconcile::list_zip_files(&mods_folder)?)?;
+    let inactive_dir = profiles::inactive_dir()?;
+    let inactive = if inactive_dir.is_dir() { backed_up_files(&inactive_dir, profiles::inactive_files()?)? } else { Vec::new() };
+
+    let created_at = fsutil::unix_now();
+    let mut backup = ModsBackup {
+        id: STORE.new_id(created_at)?,
+        created_at,
+        label: label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()),
+        files,
+        inactive,
+        has_db: mods_folder.join(MOD_DB_FILENAME).is_file(),
+        has_manifest: manifest::manifest_path()?.is_file(),
+        automatic,
+        size: 0,
+    };
+    STORE
+        .write(&mut backup, |zip, backup| write_backup(zip, &mods_folder, backup))
+        .map_err(|e| format!("Backing up the mods folder failed: {}", e))?;
+    Ok(backup)
+}
+
+#[tauri::command]
+pub async fn create_mods_backup(label: Option<String>) -> Result<ModsBackup, String> {
+    game::ensure_game_not_running()?;
+    fsutil::blocking(move || create_backup(label, false)).await
+}
+
+// Backups on disk, newest first. Zips that cannot be read are skipped.
+#[tauri::command]
+pub fn list_mods_backups() -> Result<Vec<ModsBackup>, String> {
+    STORE.list()
+}
+
+#[tauri::command]
+pub fn delete_mods_backup(id: String) -> Result<(), String> {
+    STORE.delete(&id)
+}
+
+#[tauri::command]
+pub async fn preview_mods_backup(id: String) -> Result<RestorePreview, String> {
+    fsutil::blocking(move || {
+        let backup: ModsBackup = backups::read_metadata(&STORE.path(&id)?)?;
+        preview(&backup, &settings::mods_folder()?)
+    })
+    .await
+}
+
+// Write the backup's files, db.json and manifest back, skipping zips that
+// could not be moved aside.
+fn write_back(path: &Path, backup: &ModsBackup, preview: &RestorePreview, mods_folder: &Path, failed: &mut Vec<RestoreFailure>) -> Result<(), String> {
+    let mut archive = ZipArchive::new(File::open(path).map_err(|e| e.to_string())?).map_err(|e| e.to_string())?;
+    for filename in preview.added.iter().chain(&preview.overwritten) {
+        // A file that could not be moved aside is left as it is.
+        if failed.iter().any(|f| f.filename == *filename) {
+            continue;
+        }
+        let name = format!("{}{}", MODS_PREFIX, filename);
+        if let Err(error) = extract_file(&mut archive, &name, &mods_folder.join(filename)) {
+            failed.push(RestoreFailure {
+                filename: filename.clone(),
+                error,
+            });
+        }
+    }
+    if !preview.inactive.is_empty() {
+        let inactive_dir = profiles::inactive_dir()?;
+        fs::create_dir_all(&inactive_dir).map_err(|e| e.to_string())?;
+        for filename in &preview.inactive {
+            let name = format!("{}{}", INACTIVE_PREFIX, filename);
+            if let Err(error) = extract_file(&mut archive, &name, &inactive_dir.join(filename)) {
+                failed.push(RestoreFailure {
+                    filename: filename.clone(),
+                    error,
+                });
+            }
+        }
+    }
+    if backup.has_db {
+        let name = format!("{}{}", MODS_PREFIX, MOD_DB_FILENAME);
+        if let Err(error) = extract_file(&mut archive, &name, &mods_folder.join(MOD_DB_FILENAME)) {
+            failed.push(RestoreFailure {
+                filename: MOD_DB_FILENAME.to_string(),
+                error,
+            });
+        }
+    }
+    if backup.has_manifest {
+        // Saved through the manifest module so the current one is kept in
+        // its backup rotation.
+        let mut content = String::new();
+        let restored = archive
+            .by_name(manifest::MANIFEST_FILENAME)
+            .map_err(|e| e.to_string())
+            .and_then(|mut entry| entry.read_to_string(&mut content).map_err(|e| e.to_string()))
+            .and_then(|_| manifest::parse(&content))
+            .and_then(|parsed| manifest::save(&manifest::manifest_path()?, &parsed));
+        if let Err(error) = restored {
+            failed.push(RestoreFailure {
+                filename: manifest::MANIFEST_FILENAME.to_string(),
+                error,
+            });
+        }
+    }
+    Ok(())
+}
+
+// Make the mods folder match a backup: zips it does not contain and the
+// ones it replaces go to the installer trash, then its files, db.json and
+// manifest are written back. The current state is backed up first; only
+// the newest few of those safety backups are kept. Files that fail are
+// reported and the rest carry on.
+#[tauri::command]
+pub async fn restore_mods_backup(id: String) -> Result<RestoreReport, String> {
+    game::ensure_game_not_running()?;
+    let path = STORE.path(&id)?;
+    let mods_folder = settings::mods_folder()?;
+    let (backup, preview, safety_backup) = {
+        let (path, mods_folder, id) = (path.clone(), mods_folder.clone(), id.clone());
+        fsutil::blocking(move || {
+            let backup: ModsBackup = backups::read_metadata(&path)?;
+            let preview = preview(&backup, &mods_folder)?;
+            let safety_backup = create_backup(Some(format!("Before restoring {}", id)), true)?;
+            Ok((backup, preview, safety_backup))
+        })
+        .await?
+    };
+    let mut failed = Vec::new();
+
+    for filename in preview.removed.iter().chain(&preview.overwritten) {
+        let outcome = trash(&mods_folder, filename).await;
+        if let DeleteOutcome::Locked { error, .. } | DeleteOutcome::Failed { error, .. } = outcome {
+            failed.push(RestoreFailure {
+                filename: filename.clone(),
+                error,
+            });
+        }
+    }
+
+    let (preview, failed) = fsutil::blocking(move || {
+        write_back(&path, &backup, &preview, &mods_folder, &mut failed)?;
+        // Only now: the backup just restored may be an old safety backup.
+        if let Err(e) = STORE.prune(MAX_SAFETY_BACKUPS, |b: &ModsBackup| b.automatic) {
+            eprintln!("Failed to prune mods backups: {}", e);
+        }
+        Ok((preview, failed))
+    })
+    .await?;
+
+    Ok(RestoreReport {
+        preview,
+        safety_backup,
+        failed,
+    })
+}
@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{manifest, profiles, settings};
+
+// Per-user RLS Installer data directory under LOCALAPPDATA. Everything
+// the installer owns (manifest, pending deletions, ...) lives here so it
+// survives reinstalls of the app itself.
+pub fn data_dir() -> Result<PathBuf, String> {
+    let local_app_data = std::env::var("LOCALAPPDATA").map_err(|e| e.to_string())?;
+    let data_dir = PathBuf::from(local_app_data).join("RLS Installer");
+    fs::create_dir_all(&data_dir).map_err(|e| e.to_string())?;
+    Ok(data_dir)
+}
+
+// Default BeamNG mods folder on Windows:
+// %LOCALAPPDATA%/BeamNG/BeamNG.drive/current/mods
+pub fn default_mods_folder() -> Result<PathBuf, String> {
+    let local_app_data = std::env::var("LOCALAPPDATA").map_err(|e| e.to_string())?;
+    Ok(PathBuf::from(local_app_data)
+        .join("BeamNG")
+        .join("BeamNG.drive")
+        .join("current") // "latest" is often a symlink or folder in BeamNG user path
+        .join("mods"))
+}
+
+// Folder the game creates in its user folder on first launch.
+const USER_FOLDER_MARKER: &str = "settings";
+
+// BeamNG user folder (`.../BeamNG.drive/current`), the parent of the
+// configured mods folder. Cache clearing and save restores delete and
+// rename folders in here, so a parent that is not a user folder (a mods
+// folder set to e.g. `D:\Games\mods`) is refused.
+pub fn user_folder() -> Result<PathBuf, String> {
+    let mods_folder = settings::mods_folder()?;
+    let user_folder = mods_folder
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("{} has no parent folder", mods_folder.display()))?;
+    if !user_folder.join(USER_FOLDER_MARKER).is_dir() {
+        return Err(format!(
+            "{} does not look like a BeamNG user folder (no {} folder); point the mods folder at the one inside the game's user folder",
+            user_folder.display(),
+            USER_FOLDER_MARKER
+        ));
+    }
+    Ok(user_folder)
+}
+
+// Canonicalize a path that may not exist yet by resolving its nearest
+// existing ancestor and re-appending the rest. `..` in the non-existing
+// tail is rejected since it cannot be resolved safely.
+fn canonicalize_lenient(path: &Path) -> Result<PathBuf, String> {
+    let mut existing = path;
+    let mut tail: Vec<&std::ffi::OsStr> = Vec::new();
+
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return Ok(tail.iter().rev().fold(canonical, |acc, part| acc.join(part)));
+        }
+        let Some(parent) = existing.parent() else {
+            return Err(format!("Cannot resolve path {}", path.display()));
+        };
+        match existing.components().next_back() {
+            Some(Component::Normal(part)) => tail.push(part),
+            _ => return Err(format!("Cannot resolve path {}", path.display())),
+        }
+        existing = parent;
+    }
+}
+
+// Directories the webview may point file commands at: the installer data
+// directory and the configured BeamNG mods folder.
+fn allowed_roots() -> Vec<PathBuf> {
+    [data_dir(), settings::mods_folder()]
+        .into_iter()
+        .filter_map(|root| root.ok())
+        .filter_map(|root| canonicalize_lenient(&root).ok())
+        .collect()
+}
+
+fn resolve_absolute(path: &str) -> Result<PathBuf, String> {
+    let requested = PathBuf::from(path);
+    if !requested.is_absolute() {
+        return Err(format!("Access denied: {} is not an absolute path", path));
+    }
+    canonicalize_lenient(&requested)
+}
+
+// Resolve a path received from the frontend and refuse anything outside
+// the allowed roots. Every command that takes a file path from the
+// webview must go through this.
+pub fn resolve_allowed(path: &str) -> Result<PathBuf, String> {
+    let resolved = resolve_absolute(path)?;
+    if allowed_roots().iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        Err(format!(
+            "Access denied: {} is outside the RLS Installer data folder and the BeamNG mods folder",
+            path
+        ))
+    }
+}
+
+// Like resolve_allowed, for commands that create, replace or delete the
+// file. Only the mods folder qualifies: the data folder holds the
+// manifest, settings, pending deletions, trash metadata and backups, which
+// the webview changes through their own typed commands.
+pub fn resolve_writable(path: &str) -> Result<PathBuf, String> {
+    let resolved = resolve_absolute(path)?;
+    let in_mods_folder = settings::mods_folder()
+        .and_then(|root| canonicalize_lenient(&root))
+        .is_ok_and(|root| resolved.starts_with(root));
+    if !in_mods_folder {
+        return Err(format!("Access denied: {} is outside the BeamNG mods folder", path));
+    }
+    // Keep the requested spelling of the file name: resolving an existing
+    // file returns its name as stored, which would turn a case-only rename
+    // into a no-op.
+    let requested = Path::new(path);
+    match (requested.parent(), requested.file_name()) {
+        (Some(parent), Some(name)) => Ok(canonicalize_lenient(parent)?.join(name)),
+        _ => Ok(resolved),
+    }
+}
+
+// For the raw manifest commands: the path must be the live manifest, so
+// recovering from a damaged file never touches any other state file.
+pub fn resolve_manifest(path: &str) -> Result<PathBuf, String> {
+    let resolved = resolve_absolute(path)?;
+    let manifest = manifest::manifest_path()?;
+    if canonicalize_lenient(&manifest)? == resolved {
+        Ok(manifest)
+    } else {
+        Err(format!("Access denied: {} is not the installer manifest", path))
+    }
+}
+
+// Check a path the installer read back from its own state files (pending
+// deletions, trash entries) before acting on it; those files are on disk
+// and may have been tampered with. The store of mods disabled by a
+// profile is allowed alongside the mods folder.
+pub fn confine_to_mods(path: &Path) -> Result<PathBuf, String> {
+    let resolved = canonicalize_lenient(path)?;
+    let allowed = [settings::mods_folder(), profiles::inactive_dir()]
+        .into_iter()
+        .filter_map(|root| root.ok())
+        .filter_map(|root| canonicalize_lenient(&root).ok())
+        .any(|root| resolved.starts_with(root));
+    if allowed {
+        Ok(resolved)
+    } else {
+        Err(format!("Refusing to touch {} outside the BeamNG mods folder", path.display()))
+    }
+}
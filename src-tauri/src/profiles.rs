@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::manifest::{self, Manifest};
+use crate::{fsutil, game, paths, reconcile, settings};
+
+const PROFILES_FILENAME: &str = "profiles.json";
+// Zips of disabled mods the game's db.json does not know about are kept
+// here, outside the mods folder, until a profile enables them again.
+const INACTIVE_DIRNAME: &str = "inactive_mods";
+// BeamNG's own list of mods, with an `active` flag per zip.
+const MOD_DB_FILENAME: &str = "db.json";
+
+// Serializes read-modify-write cycles of profiles.json.
+static PROFILES_LOCK: Mutex<()> = Mutex::new(());
+
+// A named set of mods to keep active; everything else installed in the
+// mods folder is disabled when switching to it.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Profile {
+    pub name: String,
+    // Manifest mod ids, so a profile survives mod updates.
+    #[serde(default)]
+    pub mods: BTreeSet<String>,
+    // Zips not tracked by the manifest.
+    #[serde(default)]
+    pub files: BTreeSet<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct ProfileStore {
+    #[serde(default)]
+    pub active: Option<String>,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ToggleMethod {
+    // The `active` flag of the zip's entry in mods/db.json.
+    DbJson,
+    // The zip was moved into or out of the inactive store.
+    Moved,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileChange {
+    pub filename: String,
+    pub mod_id: Option<String>,
+    pub enabled: bool,
+    pub method: ToggleMethod,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ProfileFailure {
+    pub filename: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct SwitchReport {
+    pub profile: String,
+    pub changes: Vec<ProfileChange>,
+    // Profile mods that are not installed.
+    pub missing: Vec<String>,
+    pub failed: Vec<ProfileFailure>,
+}
+
+// Where a zip currently is and whether the game loads it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileState {
+    Active,
+    // In the mods folder, disabled in db.json.
+    DisabledInDb,
+    // In the inactive store.
+    Stored,
+}
+
+fn profiles_path() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(PROFILES_FILENAME))
+}
+
+pub fn inactive_dir() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(INACTIVE_DIRNAME))
+}
+
+// Zips currently parked in the inactive store.
+pub fn inactive_files() -> Result<Vec<String>, String> {
+    reconcile::list_zip_files(&inactive_dir()?)
+}
+
+fn load_store() -> ProfileStore {
+    profiles_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn update_store<F>(change: F) -> Result<ProfileStore, String>
+where
+    F: FnOnce(&mut ProfileStore) -> Result<(), String>,
+{
+    let _guard = PROFILES_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut store = load_store();
+    change(&mut store)?;
+    let content = serde_json::to_string_pretty(&store).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&profiles_path()?, content.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(store)
+}
+
+// Key of the db.json entry for each zip filename (lowercase), found via
+// the entry's `filename` or the last segment of its `fullpath`.
+fn db_entries(db: &Value) -> BTreeMap<String, String> {
+    let Some(mods) = db.get("mods").and_then(Value::as_object) else {
+        return BTreeMap::new();
+    };
+    mods.iter()
+        .filter_map(|(key, entry)| {
+            let filename = entry
+                .get("filename")
+                .and_then(Value::as_str)
+                .or_else(|| entry.get("fullpath").and_then(Value::as_str)?.rsplit('/').next())?;
+            Some((filename.to_lowercase(), key.clone()))
+        })
+        .collect()
+}
+
+fn db_active(db: &Value, key: &str) -> bool {
+    db.pointer(&format!("/mods/{}/active", key.replace('~', "~0").replace('/', "~1")))
+        .and_then(Value::as_bool)
+        .unwrap_or(true)
+}
+
+// Filenames a profile wants active: its unmanaged files, plus the current
+// file of every listed mod that is installed.
+pub fn wanted_files(profile: &Profile, installed: &Manifest) -> BTreeSet<String> {
+    profile
+        .mods
+        .iter()
+        .filter_map(|id| installed.mods.get(id).map(|entry| entry.filename.clone()))
+        .chain(profile.files.iter().cloned())
+        .collect()
+}
+
+// Profile from the set of zips the game currently loads.
+pub fn capture(name: String, installed: &Manifest, states: &BTreeMap<String, FileState>) -> Profile {
+    let active: BTreeSet<&str> = states
+        .iter()
+        .filter(|(_, state)| **state == FileState::Active)
+        .map(|(file, _)| file.as_str())
+        .collect();
+    let managed: BTreeMap<&str, &str> = installed
+        .mods
+        .values()
+        .map(|entry| (entry.filename.as_str(), entry.id.as_str()))
+        .collect();
+    Profile {
+        name,
+        mods: active.iter().filter_map(|file| managed.get(file)).map(|id| id.to_string()).collect(),
+        files: active.iter().filter(|file| !managed.contains_key(*file)).map(|file| file.to_string()).collect(),
+    }
+}
+
+struct ModsState {
+    mods_folder: PathBuf,
+    db_path: PathBuf,
+    db: Option<Value>,
+    states: BTreeMap<String, FileState>,
+}
+
+fn read_state() -> Result<ModsState, String> {
+    let mods_folder = settings::mods_folder()?;
+    let db_path = mods_folder.join(MOD_DB_FILENAME);
+    let db: Option<Value> = fs::read_to_string(&db_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    let mut states = BTreeMap::new();
+    let keys = db.as_ref().map(db_entries).unwrap_or_default();
+    for file in reconcile::list_zip_files(&mods_folder)? {
+        let disabled = match (&db, keys.get(&file.to_lowercase())) {
+            (Some(db), Some(key)) => !db_active(db, key),
+            _ => false,
+        };
+        states.insert(file, if disabled { FileState::DisabledInDb } else { FileState::Active });
+    }
+    for file in inactive_files()? {
+        states.entry(file).or_insert(FileState::Stored);
+    }
+    Ok(ModsState {
+        mods_folder,
+        db_path,
+        db,
+        states,
+    })
+}
+
+fn set_db_active(db: &mut Value, key: &str, active: bool) {
+    if let Some(entry) = db.get_mut("mods").and_then(|mods| mods.get_mut(key)).and_then(Value::as_object_mut) {
+        entry.insert("active".to_string(), Value::Bool(active));
+    }
+}
+
+fn move_zip(from: &Path, to_dir: &Path, filename: &str) -> Result<(), String> {
+    fs::create_dir_all(to_dir).map_err(|e| e.to_string())?;
+    fsutil::move_file(from, &to_dir.join(filename))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_profiles() -> ProfileStore {
+    load_store()
+}
+
+// Create or replace the profile with this name.
+#[tauri::command]
+pub fn save_profile(profile: Profile) -> Result<ProfileStore, String> {
+    let name = profile.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    update_store(|store| {
+        let profile = Profile { name: name.clone(), ..profile };
+        match store.profiles.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = profile,
+            None => store.profiles.push(profile),
+        }
+        Ok(())
+    })
+}
+
+// Save the mods the game currently loads as a profile.
+#[tauri::command]
+pub fn capture_profile(name: String) -> Result<ProfileStore, String> {
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let state = read_state()?;
+    let profile = capture(name.clone(), &installed, &state.states);
+    save_profile(profile)?;
+    update_store(|store| {
+        store.active = Some(name.trim().to_string());
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn delete_profile(name: String) -> Result<ProfileStore, String> {
+    update_store(|store| {
+        store.profiles.retain(|p| p.name != name);
+        if store.active.as_deref() == Some(name.as_str()) {
+            store.active = None;
+        }
+        Ok(())
+    })
+}
+
+// Enable exactly the mods of a profile and disable every other zip in the
+// mods folder. Zips BeamNG lists in mods/db.json are toggled through
+// their `active` flag; the others are moved to or from the inactive
+// store. Files that cannot be changed are reported and the rest carry on.
+#[tauri::command]
+pub async fn switch_profile(name: String) -> Result<SwitchReport, String> {
+    game::ensure_game_not_running()?;
+    fsutil::blocking(move || switch(&name)).await
+}
+
+fn switch(name: &str) -> Result<SwitchReport, String> {
+    let store = load_store();
+    let profile = store
+        .profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| format!("No profile named {}", name))?;
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+    let wanted = wanted_files(&profile, &installed);
+    let mut state = read_state()?;
+    let keys = state.db.as_ref().map(db_entries).unwrap_or_default();
+    let inactive = inactive_dir()?;
+    let mod_of = |file: &str| installed.mods.values().find(|e| e.filename == file).map(|e| e.id.clone());
+
+    let mut report = SwitchReport {
+        profile: profile.name.clone(),
+        changes: Vec::new(),
+        missing: profile.mods.iter().filter(|id| !installed.mods.contains_key(*id)).cloned().collect(),
+        failed: Vec::new(),
+    };
+    report.missing.extend(profile.files.iter().filter(|f| !state.states.contains_key(*f)).cloned());
+
+    let mut db_changed = false;
+    for (file, current) in &state.states {
+        let enable = wanted.contains(file);
+        let db_key = keys.get(&file.to_lowercase());
+        let outcome = match (enable, current, &mut state.db, db_key) {
+            (true, FileState::Active, _, _) | (false, FileState::DisabledInDb | FileState::Stored, _, _) => continue,
+            (true, FileState::Stored, db, key) => {
+                let moved = move_zip(&inactive.join(file), &state.mods_folder, file);
+                // A db.json entry left disabled would keep it off.
+                if let (Ok(()), Some(db), Some(key)) = (&moved, db, key) {
+                    set_db_active(db, key, true);
+                    db_changed = true;
+                }
+                moved.map(|_| ToggleMethod::Moved)
+            }
+            (_, _, Some(db), Some(key)) => {
+                set_db_active(db, key, enable);
+                db_changed = true;
+                Ok(ToggleMethod::DbJson)
+            }
+            (false, FileState::Active, _, _) => move_zip(&state.mods_folder.join(file), &inactive, file).map(|_| ToggleMethod::Moved),
+            // db.json disappeared between reading and now.
+            (true, FileState::DisabledInDb, _, _) => continue,
+        };
+        match outcome {
+            Ok(method) => report.changes.push(ProfileChange {
+                filename: file.clone(),
+                mod_id: mod_of(file),
+                enabled: enable,
+                method,
+            }),
+            Err(error) => report.failed.push(ProfileFailure {
+                filename: file.clone(),
+                error,
+            }),
+        }
+    }
+
+    if let (true, Some(db)) = (db_changed, &state.db) {
+        let content = serde_json::to_string_pretty(db).map_err(|e| e.to_string())?;
+        fsutil::write_atomic(&state.db_path, content.as_bytes()).map_err(|e| e.to_string())?;
+    }
+    update_store(|store| {
+        store.active = Some(profile.name.clone());
+        Ok(())
+    })?;
+    Ok(report)
+}
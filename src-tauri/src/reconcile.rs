@@ -0,0 +1,458 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::catalog::ModConfig;
+use crate::manifest::{self, Manifest, ManifestEntry};
+use crate::{profiles, settings};
+use crate::version::Version;
+
+// Version shapes found in mod zip names, tried in this order.
+static HOTFIX_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)_(\d+(?:\.\d+)+_hotfix)\s*$").unwrap());
+static COMPLEX_VERSION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)_(\d+\.\d+(?:[._-](?:beta|alpha|rc)?[._-]?\d+)?)\s*$").unwrap());
+static PREFIXED_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)v(\d+(?:[._-]\d+)*)").unwrap());
+static DOTTED_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+\.\d+(?:[._]\d+)*)$").unwrap());
+static PHASE_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(beta|alpha|rc)[_-]?(\d+)").unwrap());
+static DATE_VERSION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[a-zA-Z]+_\d{1,2}_\d{4}").unwrap());
+
+// Best guess at the version encoded in a mod zip name, e.g.
+// `rls_career_overhaul_2.6.2_hotfix.zip` -> `2.6.2_hotfix`.
+pub fn extract_version(filename: &str) -> Option<String> {
+    let name = match filename.len().checked_sub(4) {
+        Some(cut) if filename.is_char_boundary(cut) && filename[cut..].eq_ignore_ascii_case(".zip") => &filename[..cut],
+        _ => filename,
+    };
+
+    if let Some(caps) = HOTFIX_VERSION.captures(name) {
+        return Some(caps[1].to_string());
+    }
+    if let Some(caps) = COMPLEX_VERSION.captures(name) {
+        return Some(caps[1].replace('_', "."));
+    }
+    if let Some(caps) = PREFIXED_VERSION.captures(name) {
+        return Some(caps[1].to_string());
+    }
+    if let Some(caps) = DOTTED_VERSION.captures(name) {
+        return Some(caps[1].to_string());
+    }
+    if let Some(caps) = PHASE_VERSION.captures(name) {
+        return Some(format!("{}_{}", &caps[1], &caps[2]));
+    }
+    if let Some(m) = DATE_VERSION.find(name) {
+        return Some(m.as_str().to_string());
+    }
+
+    let last = name.rsplit(['_', '-']).next().unwrap_or(name);
+    if last.chars().any(|c| c.is_ascii_digit()) && last.chars().count() < 15 {
+        return Some(last.to_string());
+    }
+    None
+}
+
+struct Matcher<'a> {
+    id: &'a str,
+    pattern: Option<Regex>,
+    // Longer patterns are more specific; mods without a pattern only
+    // match by name and lose to any pattern match.
+    specificity: usize,
+}
+
+impl Matcher<'_> {
+    fn matches(&self, filename: &str) -> bool {
+        match &self.pattern {
+            Some(pattern) => pattern.is_match(filename),
+            None => {
+                let needle = self.id.to_lowercase().replace("rls_", "");
+                !needle.is_empty() && filename.to_lowercase().contains(&needle)
+            }
+        }
+    }
+}
+
+fn build_matchers(catalog: &[ModConfig]) -> Vec<Matcher<'_>> {
+    catalog
+        .iter()
+        .filter_map(|config| match config.asset_pattern.as_deref() {
+            None | Some("") => Some(Matcher { id: &config.id, pattern: None, specificity: 0 }),
+            Some(source) => match RegexBuilder::new(source).case_insensitive(true).build() {
+                Ok(pattern) => Some(Matcher { id: &config.id, pattern: Some(pattern), specificity: source.len() }),
+                Err(e) => {
+                    eprintln!("Invalid assetPattern for mod {}: {}", config.id, e);
+                    None
+                }
+            },
+        })
+        .collect()
+}
+
+// Catalog mods a file belongs to: every match of the highest specificity,
+// sorted by id. More than one means the catalog is ambiguous for it.
+fn candidates_for(matchers: &[Matcher], filename: &str) -> Vec<String> {
+    let matching: Vec<&Matcher> = matchers.iter().filter(|m| m.matches(filename)).collect();
+    let Some(best) = matching.iter().map(|m| m.specificity).max() else {
+        return Vec::new();
+    };
+    let ids: BTreeSet<&str> = matching.iter().filter(|m| m.specificity == best).map(|m| m.id).collect();
+    ids.into_iter().map(str::to_string).collect()
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct VersionChange {
+    pub mod_id: String,
+    pub filename: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct AmbiguousMatch {
+    pub filename: String,
+    // Mods the file could belong to.
+    pub candidates: Vec<String>,
+    // Key the file ended up under in the manifest.
+    pub assigned_to: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ReconcileDiff {
+    pub added: Vec<ManifestEntry>,
+    pub removed: Vec<ManifestEntry>,
+    pub version_changed: Vec<VersionChange>,
+    // Files no catalog mod matched, tracked under their own filename.
+    pub unmatched: Vec<String>,
+    pub ambiguous: Vec<AmbiguousMatch>,
+}
+
+impl ReconcileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.version_changed.is_empty()
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct Reconciliation {
+    pub manifest: Manifest,
+    pub diff: ReconcileDiff,
+    pub applied: bool,
+}
+
+// Add a newly found file to the manifest under `key`.
+fn track(
+    manifest: &mut Manifest,
+    diff: &mut ReconcileDiff,
+    candidates: &mut BTreeMap<String, Vec<String>>,
+    key: String,
+    filename: String,
+    version: String,
+) {
+    if manifest.mods.contains_key(&key) {
+        eprintln!("Not tracking {}: manifest key {} is already taken", filename, key);
+        return;
+    }
+    let found = candidates.remove(&filename).unwrap_or_default();
+    if found.len() > 1 || found.first().is_some_and(|id| *id != key) {
+        diff.ambiguous.push(AmbiguousMatch {
+            filename: filename.clone(),
+            candidates: found,
+            assigned_to: key.clone(),
+        });
+    }
+    let entry = ManifestEntry {
+        id: key.clone(),
+        version,
+        filename,
+        ..Default::default()
+    };
+    diff.added.push(entry.clone());
+    manifest.mods.insert(key, entry);
+}
+
+// Bring the manifest in line with the zips actually in the mods folder.
+//
+// Rules, applied in a fixed order so the same inputs always give the same
+// manifest:
+// 1. Entries whose file is gone are removed.
+// 2. Entries the installer did not install take their version from the
+//    filename; installed ones keep the version recorded at install time.
+// 3. Each untracked file goes to the catalog mod with the most specific
+//    matching `assetPattern`, ties broken by mod id and reported as
+//    ambiguous. Unmatched files are tracked under their filename.
+// 4. A mod that still has its tracked file keeps it. Otherwise, of the new
+//    files claiming it, the one with the newest version wins, then the
+//    last by name. Losing files are tracked under their filename.
+pub fn reconcile(manifest: &Manifest, files: &[String], catalog: &[ModConfig]) -> Reconciliation {
+    let files: BTreeSet<&str> = files.iter().map(String::as_str).collect();
+    let mut result = manifest.clone();
+    let mut diff = ReconcileDiff::default();
+
+    result.mods.retain(|_, entry| {
+        let present = files.contains(entry.filename.as_str());
+        if !present {
+            diff.removed.push(entry.clone());
+        }
+        present
+    });
+
+    for (id, entry) in result.mods.iter_mut() {
+        if entry.installed_by_installer {
+            continue;
+        }
+        let version = extract_version(&entry.filename).unwrap_or_else(|| "Unknown".to_string());
+        if entry.version != version {
+            diff.version_changed.push(VersionChange {
+                mod_id: id.clone(),
+                filename: entry.filename.clone(),
+                from: std::mem::replace(&mut entry.version, version.clone()),
+                to: version,
+            });
+        }
+    }
+
+    let tracked: BTreeSet<String> = result.mods.values().map(|e| e.filename.clone()).collect();
+    let matchers = build_matchers(catalog);
+
+    // Group untracked files by the mod they belong to.
+    let mut claims: BTreeMap<String, Vec<(String, String)>> = BTreeMap::new();
+    let mut candidates: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for filename in files.iter().filter(|f| !tracked.contains(**f)) {
+        let found = candidates_for(&matchers, filename);
+        let key = match found.first() {
+            Some(id) => id.clone(),
+            None => {
+                diff.unmatched.push(filename.to_string());
+                filename.to_string()
+            }
+        };
+        let version = extract_version(filename).unwrap_or_else(|| "Unknown".to_string());
+        claims.entry(key).or_default().push((filename.to_string(), version));
+        candidates.insert(filename.to_string(), found);
+    }
+
+    for (key, mut files) in claims {
+        let winner = if result.mods.contains_key(&key) {
+            None
+        } else {
+            files
+                .iter()
+                .enumerate()
+                .max_by(|(_, a), (_, b)| (Version::parse(&a.1), &a.0).cmp(&(Version::parse(&b.1), &b.0)))
+                .map(|(index, _)| index)
+        };
+        if let Some(index) = winner {
+            let (filename, version) = files.remove(index);
+            track(&mut result, &mut diff, &mut candidates, key, filename, version);
+        }
+        for (filename, version) in files {
+            track(&mut result, &mut diff, &mut candidates, filename.clone(), filename, version);
+        }
+    }
+
+    Reconciliation {
+        manifest: result,
+        diff,
+        applied: false,
+    }
+}
+
+// Names of the zip files directly inside `dir`, sorted.
+pub fn list_zip_files(dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let is_zip = path
+            .extension()
+            .is_some_and(|ext| ext.to_string_lossy().eq_ignore_ascii_case("zip"));
+        if path.is_file() && is_zip {
+            if let Some(name) = path.file_name() {
+                files.push(name.to_string_lossy().to_string());
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// Reconcile the stored manifest with the configured mods folder, saving
+// the result when `apply` is set and something changed.
+#[tauri::command]
+pub fn reconcile_mods(catalog: Vec<ModConfig>, apply: bool) -> Result<Reconciliation, String> {
+    // Mods disabled by a profile are parked outside the mods folder but
+    // still installed.
+    let mut files = list_zip_files(&settings::mods_folder()?)?;
+    files.extend(profiles::inactive_files()?);
+    files.sort();
+    files.dedup();
+
+    if !apply {
+        return Ok(reconcile(&manifest::load(&manifest::manifest_path()?)?, &files, &catalog));
+    }
+    // Reconciled under the manifest lock so a concurrent install is not
+    // overwritten with an older list.
+    let mut result = None;
+    manifest::update(|installed| {
+        let mut reconciliation = reconcile(installed, &files, &catalog);
+        if !reconciliation.diff.is_empty() {
+            *installed = reconciliation.manifest.clone();
+            reconciliation.applied = true;
+        }
+        result = Some(reconciliation);
+        Ok(())
+    })?;
+    result.ok_or_else(|| "Reconciling the manifest failed".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::config;
+
+    const CAREER_PATTERN: &str = r"^rls_career_overhaul_.*\.zip$";
+
+    fn entry(id: &str, version: &str, filename: &str, installed_by_installer: bool) -> ManifestEntry {
+        ManifestEntry {
+            id: id.to_string(),
+            version: version.to_string(),
+            filename: filename.to_string(),
+            installed_by_installer,
+            ..Default::default()
+        }
+    }
+
+    fn manifest(entries: Vec<ManifestEntry>) -> Manifest {
+        Manifest {
+            mods: entries.into_iter().map(|e| (e.id.clone(), e)).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn files(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn removes_entries_whose_file_is_gone() {
+        let current = manifest(vec![entry("career", "2.6.1", "rls_career_overhaul_2.6.1.zip", true)]);
+
+        let result = reconcile(&current, &[], &[config("career", Some(CAREER_PATTERN))]);
+
+        assert!(result.manifest.mods.is_empty());
+        assert_eq!(result.diff.removed.len(), 1);
+        assert_eq!(result.diff.removed[0].id, "career");
+        assert!(!result.diff.is_empty());
+    }
+
+    #[test]
+    fn adds_files_matching_the_catalog() {
+        let catalog = [config("career", Some(CAREER_PATTERN))];
+
+        let result = reconcile(&Manifest::default(), &files(&["rls_career_overhaul_2.6.2.zip"]), &catalog);
+
+        assert_eq!(result.diff.added.len(), 1);
+        let added = &result.manifest.mods["career"];
+        assert_eq!(added.filename, "rls_career_overhaul_2.6.2.zip");
+        assert_eq!(added.version, "2.6.2");
+        assert!(result.diff.unmatched.is_empty());
+        assert!(result.diff.ambiguous.is_empty());
+    }
+
+    #[test]
+    fn takes_version_from_filename_only_for_discovered_mods() {
+        let current = manifest(vec![
+            entry("career", "2.6.1", "rls_career_overhaul_2.6.2.zip", false),
+            entry("traffic", "1.0", "traffic_1.5.zip", true),
+        ]);
+        let names = files(&["rls_career_overhaul_2.6.2.zip", "traffic_1.5.zip"]);
+
+        let result = reconcile(&current, &names, &[]);
+
+        assert_eq!(result.diff.version_changed.len(), 1);
+        let change = &result.diff.version_changed[0];
+        assert_eq!((change.mod_id.as_str(), change.from.as_str(), change.to.as_str()), ("career", "2.6.1", "2.6.2"));
+        assert_eq!(result.manifest.mods["career"].version, "2.6.2");
+        assert_eq!(result.manifest.mods["traffic"].version, "1.0");
+    }
+
+    #[test]
+    fn tracks_unmatched_files_under_their_name() {
+        let catalog = [config("career", Some(CAREER_PATTERN)), config("rls_traffic", None)];
+
+        let result = reconcile(&Manifest::default(), &files(&["some_map_1.2.zip"]), &catalog);
+
+        assert_eq!(result.diff.unmatched, vec!["some_map_1.2.zip".to_string()]);
+        assert_eq!(result.manifest.mods["some_map_1.2.zip"].version, "1.2");
+    }
+
+    #[test]
+    fn reports_equally_specific_matches_as_ambiguous() {
+        // Both patterns are 13 characters long.
+        let catalog = [config("b_mod", Some(r".*career.*zip")), config("a_mod", Some(r"career.*\.zip"))];
+
+        let result = reconcile(&Manifest::default(), &files(&["career_1.0.zip"]), &catalog);
+
+        assert_eq!(result.diff.ambiguous.len(), 1);
+        let ambiguous = &result.diff.ambiguous[0];
+        assert_eq!(ambiguous.candidates, vec!["a_mod".to_string(), "b_mod".to_string()]);
+        assert_eq!(ambiguous.assigned_to, "a_mod");
+        assert!(result.manifest.mods.contains_key("a_mod"));
+        assert!(!result.manifest.mods.contains_key("b_mod"));
+    }
+
+    #[test]
+    fn more_specific_pattern_wins_without_ambiguity() {
+        let catalog = [config("generic", Some(r"career")), config("career", Some(CAREER_PATTERN))];
+
+        let result = reconcile(&Manifest::default(), &files(&["rls_career_overhaul_2.6.2.zip"]), &catalog);
+
+        assert!(result.diff.ambiguous.is_empty());
+        assert!(result.manifest.mods.contains_key("career"));
+    }
+
+    #[test]
+    fn newest_version_wins_among_new_files() {
+        let catalog = [config("career", Some(CAREER_PATTERN))];
+        let names = files(&[
+            "rls_career_overhaul_2.6.1.zip",
+            "rls_career_overhaul_2.6.10.zip",
+            "rls_career_overhaul_2.6.2.zip",
+        ]);
+
+        let result = reconcile(&Manifest::default(), &names, &catalog);
+
+        // 2.6.10 is newer than 2.6.2 even though it sorts before it by name.
+        assert_eq!(result.manifest.mods["career"].filename, "rls_career_overhaul_2.6.10.zip");
+        assert!(result.manifest.mods.contains_key("rls_career_overhaul_2.6.1.zip"));
+        assert!(result.manifest.mods.contains_key("rls_career_overhaul_2.6.2.zip"));
+        assert_eq!(result.diff.added.len(), 3);
+    }
+
+    #[test]
+    fn tracked_file_keeps_its_mod() {
+        let current = manifest(vec![entry("career", "2.6.1", "rls_career_overhaul_2.6.1.zip", true)]);
+        let names = files(&["rls_career_overhaul_2.6.1.zip", "rls_career_overhaul_2.7.0.zip"]);
+
+        let result = reconcile(&current, &names, &[config("career", Some(CAREER_PATTERN))]);
+
+        assert_eq!(result.manifest.mods["career"].filename, "rls_career_overhaul_2.6.1.zip");
+        assert!(result.manifest.mods.contains_key("rls_career_overhaul_2.7.0.zip"));
+    }
+
+    #[test]
+    fn same_inputs_give_same_manifest() {
+        let catalog = [config("career", Some(CAREER_PATTERN)), config("rls_traffic", None)];
+        let names = files(&["traffic_2.zip", "rls_career_overhaul_2.6.2.zip", "other.zip"]);
+        let mut reversed = names.clone();
+        reversed.reverse();
+
+        let a = reconcile(&Manifest::default(), &names, &catalog);
+        let b = reconcile(&Manifest::default(), &reversed, &catalog);
+
+        assert_eq!(serde_json::to_value(&a.manifest).unwrap(), serde_json::to_value(&b.manifest).unwrap());
+    }
+}
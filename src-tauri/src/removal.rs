@@ -0,0 +1,239 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::archive::{self, ArchivedVersion};
+use crate::{fsutil, paths};
+use crate::trash::{self, TrashEntry};
+
+// Windows keeps a zip locked for a short while after BeamNG, an antivirus
+// scanner or the indexer lets go of it, so give it a few chances before
+// reporting the file as locked.
+const DELETE_ATTEMPTS: u32 = 5;
+const DELETE_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+const PENDING_DELETES_FILENAME: &str = "pending_deletes.json";
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum DeleteOutcome {
+    Deleted { path: String },
+    Trashed { path: String, entry: TrashEntry },
+    Archived { path: String, entry: ArchivedVersion },
+    AlreadyAbsent { path: String },
+    // The file is still on disk. When `scheduled` is true it will be
+    // removed the next time the installer starts.
+    Locked { path: String, scheduled: bool, error: String },
+    Failed { path: String, error: String },
+}
+
+fn is_lock_error(e: &io::Error) -> bool {
+    if matches!(e.kind(), ErrorKind::PermissionDenied | ErrorKind::ResourceBusy) {
+        return true;
+    }
+    // ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(e.raw_os_error(), Some(32) | Some(33))
+}
+
+fn pending_deletes_path() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(PENDING_DELETES_FILENAME))
+}
+
+// A removal that hit a locked file, replayed with the same mode on the
+// next launch.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct PendingDelete {
+    path: String,
+    mode: RemovalMode,
+    #[serde(flatten)]
+    owner: FileOwner,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredPendingDelete {
+    Entry(PendingDelete),
+    // Older installers only stored the path of a file to delete.
+    Path(String),
+}
+
+fn read_pending_deletes(path: &Path) -> Vec<PendingDelete> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Vec<StoredPendingDelete>>(&content).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|stored| match stored {
+            StoredPendingDelete::Entry(entry) => entry,
+            StoredPendingDelete::Path(path) => PendingDelete {
+                path,
+                mode: RemovalMode::Permanent,
+                owner: FileOwner::default(),
+            },
+        })
+        .collect()
+}
+
+fn write_pending_deletes(path: &Path, pending: &[PendingDelete]) -> Result<(), String> {
+    if pending.is_empty() {
+        return match fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    }
+    let content = serde_json::to_string_pretty(pending).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(path, content.as_bytes()).map_err(|e| e.to_string())
+}
+
+fn schedule_delete(file_path: &str, mode: &RemovalMode, owner: &FileOwner) -> Result<(), String> {
+    let store = pending_deletes_path()?;
+    let mut pending = read_pending_deletes(&store);
+    pending.retain(|p| p.path != file_path);
+    pending.push(PendingDelete {
+        path: file_path.to_string(),
+        mode: mode.clone(),
+        owner: owner.clone(),
+    });
+    write_pending_deletes(&store, &pending)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalMode {
+    // Move the file into the installer trash so it can be restored later.
+    #[default]
+    Trash,
+    // Keep the file as a previous version of its mod for rollbacks.
+    Archive,
+    Permanent,
+}
+
+// Which mod a removed file belonged to, recorded alongside trashed and
+// archived copies.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct FileOwner {
+    pub mod_id: Option<String>,
+    pub version: Option<String>,
+}
+
+// One attempt at removing `path` the way `mode` asks. None when the
+// removal cannot be done at all.
+fn remove_once(path: &Path, file_path: &str, mode: &RemovalMode, owner: &FileOwner) -> Option<io::Result<DeleteOutcome>> {
+    let path_string = file_path.to_string();
+    Some(match (mode, owner) {
+        (RemovalMode::Permanent, _) => fs::remove_file(path).map(|_| DeleteOutcome::Deleted { path: path_string }),
+        (RemovalMode::Trash, owner) => trash::trash_file(path, owner.mod_id.clone(), owner.version.clone())
+            .map(|entry| DeleteOutcome::Trashed { path: path_string, entry }),
+        (RemovalMode::Archive, FileOwner { mod_id: Some(mod_id), version: Some(version) }) => {
+            archive::archive_file(path, mod_id, version).map(|entry| DeleteOutcome::Archived { path: path_string, entry })
+        }
+        (RemovalMode::Archive, _) => return None,
+    })
+}
+
+// Remove a file, retrying transient lock errors. When the file is still
+// locked after the last attempt and `schedule_if_locked` is set, it is
+// recorded so the next launch of the installer can delete it.
+pub async fn delete_file(
+    file_path: &str,
+    mode: RemovalMode,
+    owner: FileOwner,
+    schedule_if_locked: bool,
+) -> DeleteOutcome {
+    let path = PathBuf::from(file_path);
+    let mut attempt = 1;
+
+    loop {
+        if !path.exists() {
+            return DeleteOutcome::AlreadyAbsent { path: file_path.to_string() };
+        }
+
+        let Some(result) = remove_once(&path, file_path, &mode, &owner) else {
+            return DeleteOutcome::Failed {
+                path: file_path.to_string(),
+                error: "Archiving a mod requires its id and version".to_string(),
+            };
+        };
+
+        let e = match result {
+            Ok(outcome) => return outcome,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return DeleteOutcome::AlreadyAbsent { path: file_path.to_string() };
+            }
+            Err(e) => e,
+        };
+
+        if !is_lock_error(&e) {
+            return DeleteOutcome::Failed { path: file_path.to_string(), error: e.to_string() };
+        }
+
+        if attempt >= DELETE_ATTEMPTS {
+            let scheduled = schedule_if_locked
+                && match schedule_delete(file_path, &mode, &owner) {
+                    Ok(_) => true,
+                    Err(err) => {
+                        eprintln!("Failed to schedule deletion of {}: {}", file_path, err);
+                        false
+                    }
+                };
+            return DeleteOutcome::Locked {
+                path: file_path.to_string(),
+                scheduled,
+                error: e.to_string(),
+            };
+        }
+
+        tokio::time::sleep(DELETE_RETRY_DELAY * attempt).await;
+        attempt += 1;
+    }
+}
+
+// Retry removals scheduled during a previous session, trashing or
+// archiving the file as first requested. Entries that are still locked
+// stay in the list for the next launch.
+pub fn process_pending_deletes() {
+    let store = match pending_deletes_path() {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("Failed to resolve pending deletes file: {}", e);
+            return;
+        }
+    };
+
+    let pending = read_pending_deletes(&store);
+    if pending.is_empty() {
+        return;
+    }
+
+    let remaining: Vec<PendingDelete> = pending
+        .into_iter()
+        .filter(|entry| {
+            // Only ever remove inside the mods folder, whatever the list says.
+            let path = match paths::confine_to_mods(Path::new(&entry.path)) {
+                Ok(path) => path,
+                Err(e) => {
+                    eprintln!("Dropping scheduled delete: {}", e);
+                    return false;
+                }
+            };
+            match remove_once(&path, &entry.path, &entry.mode, &entry.owner) {
+                Some(Ok(_)) => false,
+                Some(Err(e)) if e.kind() == ErrorKind::NotFound => false,
+                Some(Err(e)) => {
+                    eprintln!("Scheduled delete of {} failed again: {}", entry.path, e);
+                    is_lock_error(&e)
+                }
+                None => {
+                    eprintln!("Dropping scheduled archive of {}: no mod id or version", entry.path);
+                    false
+                }
+            }
+        })
+        .collect();
+
+    if let Err(e) = write_pending_deletes(&store, &remaining) {
+        eprintln!("Failed to update pending deletes file: {}", e);
+    }
+}
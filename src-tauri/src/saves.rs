@@ -0,0 +1,244 @@
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::backups::{self, BackupMetadata, BackupStore};
+use crate::{fsutil, game, paths};
+
+const STORE: BackupStore = BackupStore {
+    dirname: "save_backups",
+    prefix: "career",
+    noun: "save backup",
+};
+
+// Backups kept after an automatic backup; older ones are pruned.
+const MAX_BACKUPS: usize = 10;
+
+// Career save folders, relative to the BeamNG user folder (the parent of
+// the mods folder).
+const SAVE_DIRS: &[&str] = &["settings/cloud/saves", "saves"];
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SaveBackup {
+    pub id: String,
+    // Unix timestamp (seconds)
+    pub created_at: u64,
+    // Why the backup was taken, e.g. before a Career Overhaul update.
+    pub reason: Option<String>,
+    // Save folders in the backup, relative to the user folder.
+    pub folders: Vec<String>,
+    pub files: usize,
+    // Size of the zip on disk.
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct RestoreResult {
+    pub restored: SaveBackup,
+    // Backup of the saves that were replaced, taken before restoring.
+    pub safety_backup: Option<SaveBackup>,
+}
+
+impl BackupMetadata for SaveBackup {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    fn set_size(&mut self, size: u64) {
+        self.size = size;
+    }
+
+    // Restoring moves these folders aside; only ever the save folders.
+    fn validate(&self) -> Result<(), String> {
+        match self.folders.iter().find(|f| !SAVE_DIRS.contains(&f.as_str())) {
+            Some(folder) => Err(format!("Save backup {} lists an unexpected folder {}", self.id, folder)),
+            None => Ok(()),
+        }
+    }
+}
+
+// Every file below `dir`, depth first.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Zip entry names always use forward slashes.
+fn entry_name(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn write_backup(zip: &mut ZipWriter<File>, user_folder: &Path, metadata: &mut SaveBackup) -> Result<(), String> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for folder in &metadata.folders {
+        let mut files = Vec::new();
+        collect_files(&user_folder.join(folder), &mut files).map_err(|e| e.to_string())?;
+        for file in files {
+            let relative = file.strip_prefix(user_folder).map_err(|e| e.to_string())?;
+            zip.start_file(entry_name(relative), options).map_err(|e| e.to_string())?;
+            io::copy(&mut File::open(&file).map_err(|e| e.to_string())?, zip).map_err(|e| e.to_string())?;
+            metadata.files += 1;
+        }
+    }
+    Ok(())
+}
+
+// Zip the career save folders into the backup directory. Returns None when
+// there are no saves to back up yet.
+pub fn create_backup(reason: Option<String>) -> Result<Option<SaveBackup>, String> {
+    let user_folder = paths::user_folder()?;
+    let folders: Vec<String> = SAVE_DIRS
+        .iter()
+        .filter(|dir| user_folder.join(dir).is_dir())
+        .map(|dir| dir.to_string())
+        .collect();
+    if folders.is_empty() {
+        return Ok(None);
+    }
+
+    let created_at = fsutil::unix_now();
+    let mut backup = SaveBackup {
+        id: STORE.new_id(created_at)?,
+        created_at,
+        reason,
+        folders,
+        files: 0,
+        size: 0,
+    };
+    STORE
+        .write(&mut backup, |zip, backup| write_backup(zip, &user_folder, backup))
+        .map_err(|e| format!("Backing up career saves failed: {}", e))?;
+    Ok(Some(backup))
+}
+
+// Backups on disk, newest first. Zips that cannot be read are skipped.
+pub fn list() -> Result<Vec<SaveBackup>, String> {
+    STORE.list()
+}
+
+// Delete all but the newest `keep` backups; returns the removed ids.
+pub fn prune(keep: usize) -> Result<Vec<String>, String> {
+    STORE.prune(keep, |_: &SaveBackup| true)
+}
+
+// Back up the saves before an update of a core mod replaces the version
+// they were made with. The update is refused if the backup fails.
+pub fn backup_before_update(mod_name: &str, from: &str, to: &str) -> Result<Option<SaveBackup>, String> {
+    let reason = format!("Before updating {} from {} to {}", mod_name, from, to);
+    let backup = create_backup(Some(reason))?;
+    if let Err(e) = prune(MAX_BACKUPS) {
+        eprintln!("Failed to prune save backups: {}", e);
+    }
+    Ok(backup)
+}
+
+// Write the files of `folders` in the backup back into the user folder.
+// Other entries are skipped: those folders were not set aside first and
+// could not be put back if the restore fails.
+fn extract(archive: &mut ZipArchive<File>, user_folder: &Path, folders: &[String]) -> Result<(), String> {
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+        // Never write outside the user folder, whatever the zip says.
+        let Some(relative) = entry.enclosed_name() else {
+            continue;
+        };
+        if entry.is_dir() || !folders.iter().any(|folder| relative.starts_with(folder)) {
+            continue;
+        }
+        let target = user_folder.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        let mut file = File::create(&target).map_err(|e| e.to_string())?;
+        io::copy(&mut entry, &mut file).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn list_save_backups() -> Result<Vec<SaveBackup>, String> {
+    list()
+}
+
+#[tauri::command]
+pub async fn create_save_backup(reason: Option<String>) -> Result<Option<SaveBackup>, String> {
+    game::ensure_game_not_running()?;
+    fsutil::blocking(move || create_backup(reason)).await
+}
+
+#[tauri::command]
+pub fn prune_save_backups(keep: usize) -> Result<Vec<String>, String> {
+    prune(keep)
+}
+
+// Replace the save folders with the ones in a backup. The current saves
+// are backed up first, and moved back if extracting fails.
+#[tauri::command]
+pub async fn restore_save_backup(id: String) -> Result<RestoreResult, String> {
+    game::ensure_game_not_running()?;
+    fsutil::blocking(move || restore(&id)).await
+}
+
+fn restore(id: &str) -> Result<RestoreResult, String> {
+    let path = STORE.path(id)?;
+    let restored: SaveBackup = backups::read_metadata(&path)?;
+    let user_folder = paths::user_folder()?;
+    let safety_backup = create_backup(Some(format!("Before restoring {}", id)))?;
+
+    let mut set_aside = Vec::new();
+    // Folders only the backup has, removed again if extracting fails.
+    let mut created = Vec::new();
+    for folder in &restored.folders {
+        let current = user_folder.join(folder);
+        if current.exists() {
+            let old = current.with_extension("restore-old");
+            let _ = fs::remove_dir_all(&old);
+            fs::rename(&current, &old).map_err(|e| e.to_string())?;
+            set_aside.push((current, old));
+        } else {
+            created.push(current);
+        }
+    }
+
+    let extracted = File::open(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|file| ZipArchive::new(file).map_err(|e| e.to_string()))
+        .and_then(|mut archive| extract(&mut archive, &user_folder, &restored.folders));
+    if let Err(e) = extracted {
+        for (current, old) in &set_aside {
+            let _ = fs::remove_dir_all(current);
+            let _ = fs::rename(old, current);
+        }
+        for folder in &created {
+            let _ = fs::remove_dir_all(folder);
+        }
+        return Err(format!("Restoring {} failed, the previous saves were kept: {}", id, e));
+    }
+    for (_, old) in set_aside {
+        if let Err(e) = fs::remove_dir_all(&old) {
+            eprintln!("Failed to remove {:?}: {}", old, e);
+        }
+    }
+
+    Ok(RestoreResult { restored, safety_backup })
+}
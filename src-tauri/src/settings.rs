@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::catalog_sources::CatalogSource;
+use crate::channels::Channel;
+use crate::{fsutil, paths};
+
+const SETTINGS_FILENAME: &str = "settings.json";
+
+// Serializes read-modify-write cycles of settings.json.
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+// User preferences persisted by the backend in the data directory.
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Settings {
+    // BeamNG mods folder chosen by the user; the detected default is used
+    // when unset.
+    #[serde(default)]
+    pub mods_folder: Option<String>,
+    // Extra mod catalogs, and overrides of the official one.
+    #[serde(default)]
+    pub catalog_sources: Vec<CatalogSource>,
+    // Personal access token for the GitHub API, to avoid its rate limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_token: Option<String>,
+    // Release channel per mod id; mods not listed follow stable.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mod_channels: BTreeMap<String, Channel>,
+    // Clear BeamNG's temp/cache folders after an update replaced a mod.
+    #[serde(default)]
+    pub clear_cache_after_update: bool,
+}
+
+fn settings_path() -> Result<PathBuf, String> {
+    Ok(paths::data_dir()?.join(SETTINGS_FILENAME))
+}
+
+pub fn load() -> Settings {
+    settings_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn update<F>(change: F) -> Result<Settings, String>
+where
+    F: FnOnce(&mut Settings),
+{
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = load();
+    change(&mut settings);
+    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    fsutil::write_atomic(&settings_path()?, content.as_bytes()).map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+pub fn mods_folder() -> Result<PathBuf, String> {
+    match load().mods_folder {
+        Some(folder) if !folder.is_empty() => Ok(PathBuf::from(folder)),
+        _ => paths::default_mods_folder(),
+    }
+}
+
+#[tauri::command]
+pub fn get_mods_folder() -> Result<String, String> {
+    Ok(mods_folder()?.to_string_lossy().to_string())
+}
+
+// Let the user pick the mods folder in a native dialog. The path never
+// comes from the webview, so a script in the UI cannot widen the set of
+// directories the file commands may touch. Returns None when cancelled.
+#[tauri::command]
+pub async fn choose_mods_folder<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> Result<Option<String>, String> {
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let mut dialog = app.dialog().file().set_title("Select BeamNG Mods Folder");
+    if let Ok(current) = mods_folder() {
+        dialog = dialog.set_directory(current);
+    }
+    dialog.pick_folder(move |folder| {
+        let _ = tx.send(folder);
+    });
+
+    let Some(folder) = rx.await.map_err(|e| e.to_string())? else {
+        return Ok(None);
+    };
+    let folder = folder.into_path().map_err(|e| e.to_string())?;
+    let folder = folder.to_string_lossy().to_string();
+
+    update(|settings| settings.mods_folder = Some(folder.clone()))?;
+    Ok(Some(folder))
+}
@@ -0,0 +1,51 @@
+// Helpers shared by the unit tests.
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::catalog::ModConfig;
+
+// Fresh, empty directory under the system temp directory, deleted again
+// when dropped so failing tests do not leave it behind either.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "rls-test-{}-{}-{}",
+            name,
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// Catalog entry with just an id (also used as the name) and an optional
+// asset pattern; tests fill in the rest with struct update syntax.
+pub fn config(id: &str, asset_pattern: Option<&str>) -> ModConfig {
+    ModConfig {
+        id: id.to_string(),
+        name: id.to_string(),
+        asset_pattern: asset_pattern.map(str::to_string),
+        ..Default::default()
+    }
+}
@@ -0,0 +1,191 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::{download, fsutil, game, paths};
+
+const TRASH_DIRNAME: &str = "trash";
+const ENTRY_METADATA_FILENAME: &str = "entry.json";
+
+// Retention policy applied every time something is moved to the trash.
+// Entries older than the age limit are purged first, then the oldest
+// remaining ones until the trash fits the size budget.
+const TRASH_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+const TRASH_MAX_TOTAL_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TrashEntry {
+    pub id: String,
+    pub filename: String,
+    pub original_path: String,
+    pub mod_id: Option<String>,
+    pub version: Option<String>,
+    // Unix timestamp (seconds) of when the file was moved to the trash.
+    pub trashed_at: u64,
+    pub size: u64,
+}
+
+#[derive(Serialize, Default)]
+pub struct TrashPurgeReport {
+    pub removed: Vec<String>,
+    pub freed_bytes: u64,
+}
+
+fn trash_dir() -> Result<PathBuf, String> {
+    let dir = paths::data_dir()?.join(TRASH_DIRNAME);
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn new_entry_id(trash_dir: &Path) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let mut id = format!("{:x}", nanos);
+    let mut suffix = 1;
+    while trash_dir.join(&id).exists() {
+        id = format!("{:x}-{}", nanos, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+fn read_entries(trash_dir: &Path) -> Vec<TrashEntry> {
+    let Ok(dirs) = fs::read_dir(trash_dir) else {
+        return Vec::new();
+    };
+    let mut entries: Vec<TrashEntry> = dirs
+        .flatten()
+        .filter_map(|dir| {
+            let content = fs::read_to_string(dir.path().join(ENTRY_METADATA_FILENAME)).ok()?;
+            let entry: TrashEntry = serde_json::from_str(&content).ok()?;
+            // The id names the directory that purging removes.
+            (dir.file_name() == entry.id.as_str()).then_some(entry)
+        })
+        .collect();
+    // Newest first
+    entries.sort_by(|a, b| b.trashed_at.cmp(&a.trashed_at).then_with(|| b.id.cmp(&a.id)));
+    entries
+}
+
+// Move `path` into its own entry directory inside the trash together with
+// the metadata needed to restore it later.
+pub fn trash_file(path: &Path, mod_id: Option<String>, version: Option<String>) -> io::Result<TrashEntry> {
+    let trash_dir = trash_dir().map_err(io::Error::other)?;
+    let filename = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| io::Error::other(format!("{} has no file name", path.display())))?;
+    let size = fs::metadata(path)?.len();
+
+    let id = new_entry_id(&trash_dir);
+    let entry_dir = trash_dir.join(&id);
+    fs::create_dir_all(&entry_dir)?;
+
+    let entry = TrashEntry {
+        id,
+        filename,
+        original_path: path.to_string_lossy().to_string(),
+        mod_id,
+        version,
+        trashed_at: fsutil::unix_now(),
+        size,
+    };
+    // Metadata first: a file in the trash without it could never be
+    // listed, restored or purged.
+    let moved = serde_json::to_string_pretty(&entry)
+        .map_err(io::Error::other)
+        .and_then(|metadata| fs::write(entry_dir.join(ENTRY_METADATA_FILENAME), metadata))
+        .and_then(|_| fsutil::move_file(path, &entry_dir.join(&entry.filename)));
+    if let Err(e) = moved {
+        let _ = fs::remove_dir_all(&entry_dir);
+        return Err(e);
+    }
+
+    if let Err(e) = apply_retention(&trash_dir) {
+        eprintln!("Failed to apply trash retention policy: {}", e);
+    }
+
+    Ok(entry)
+}
+
+fn purge_entry(trash_dir: &Path, entry: &TrashEntry, report: &mut TrashPurgeReport) -> Result<(), String> {
+    fs::remove_dir_all(trash_dir.join(&entry.id)).map_err(|e| e.to_string())?;
+    report.removed.push(entry.id.clone());
+    report.freed_bytes += entry.size;
+    Ok(())
+}
+
+fn apply_retention(trash_dir: &Path) -> Result<TrashPurgeReport, String> {
+    let now = fsutil::unix_now();
+    let mut report = TrashPurgeReport::default();
+    let mut total: u64 = 0;
+
+    // Entries are sorted newest first, so everything after the budget is
+    // exhausted is older than what we keep.
+    for entry in read_entries(trash_dir) {
+        let expired = now.saturating_sub(entry.trashed_at) > TRASH_MAX_AGE_SECS;
+        if expired || total.saturating_add(entry.size) > TRASH_MAX_TOTAL_BYTES {
+            purge_entry(trash_dir, &entry, &mut report)?;
+        } else {
+            total += entry.size;
+        }
+    }
+
+    Ok(report)
+}
+
+#[tauri::command]
+pub fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    Ok(read_entries(&trash_dir()?))
+}
+
+// Move a trashed file back to where it came from. Refuses to overwrite a
+// file that has since taken its place.
+#[tauri::command]
+pub fn restore_from_trash(entry_id: String) -> Result<TrashEntry, String> {
+    game::ensure_game_not_running()?;
+
+    let trash_dir = trash_dir()?;
+    let entry = read_entries(&trash_dir)
+        .into_iter()
+        .find(|e| e.id == entry_id)
+        .ok_or_else(|| format!("Trash entry {} not found", entry_id))?;
+
+    // entry.json is on disk and could have been edited; only ever restore
+    // into the mods folder, under the name the file has in the trash.
+    let destination = paths::confine_to_mods(Path::new(&entry.original_path))?;
+    if download::safe_filename(&entry.filename) != Some(entry.filename.as_str()) {
+        return Err(format!("Trash entry {} has an invalid file name", entry.id));
+    }
+    if destination.exists() {
+        return Err(format!("Cannot restore {}: a file with that name already exists", entry.original_path));
+    }
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    let entry_dir = trash_dir.join(&entry.id);
+    fsutil::move_file(&entry_dir.join(&entry.filename), &destination).map_err(|e| e.to_string())?;
+    fs::remove_dir_all(&entry_dir).map_err(|e| e.to_string())?;
+
+    Ok(entry)
+}
+
+// Permanently delete the given trash entries, or apply the retention
+// policy when no ids are passed.
+#[tauri::command]
+pub fn purge_trash(entry_ids: Option<Vec<String>>) -> Result<TrashPurgeReport, String> {
+    let trash_dir = trash_dir()?;
+    let Some(ids) = entry_ids else {
+        return apply_retention(&trash_dir);
+    };
+
+    let mut report = TrashPurgeReport::default();
+    for entry in read_entries(&trash_dir).iter().filter(|e| ids.contains(&e.id)) {
+        purge_entry(&trash_dir, entry, &mut report)?;
+    }
+    Ok(report)
+}
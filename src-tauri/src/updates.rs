@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::manifest;
+use crate::version::Version;
+
+// Latest version of a mod as resolved by the caller (catalog entry or
+// release lookup).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableVersion {
+    pub mod_id: String,
+    pub version: String,
+    #[serde(default)]
+    pub download_url: Option<String>,
+}
+
+// Matches the frontend `UpdateInfo`.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub mod_id: String,
+    pub new_version: String,
+    pub download_url: String,
+    pub installed_version: Option<String>,
+}
+
+// Whether `available` should replace what is installed. An unreadable
+// available version never does; an unreadable installed one is older
+// than any real version.
+pub fn is_outdated(installed: &str, available: &str) -> bool {
+    let available = Version::parse(available);
+    !available.is_unknown() && available > Version::parse(installed)
+}
+
+// Compare each available version with the manifest and return the mods
+// that need an update. Pinned mods never do. With `include_uninstalled`, mods that are not
+// installed at all are returned too.
+#[tauri::command]
+pub fn check_updates(available: Vec<AvailableVersion>, include_uninstalled: bool) -> Result<Vec<UpdateInfo>, String> {
+    let installed = manifest::load(&manifest::manifest_path()?)?;
+
+    Ok(available
+        .into_iter()
+        .filter_map(|candidate| {
+            let entry = installed.mods.get(&candidate.mod_id);
+            if entry.is_some_and(|entry| entry.pinned) {
+                return None;
+            }
+            let current = entry.map(|entry| entry.version.clone());
+            let wanted = match &current {
+                Some(version) => is_outdated(version, &candidate.version),
+                None => include_uninstalled,
+            };
+            wanted.then(|| UpdateInfo {
+                mod_id: candidate.mod_id,
+                new_version: candidate.version,
+                download_url: candidate.download_url.unwrap_or_default(),
+                installed_version: current,
+            })
+        })
+        .collect())
+}
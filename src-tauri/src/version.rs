@@ -0,0 +1,399 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// Pre-release stages, in release order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Stage {
+    // A word we do not know, such as `dev` or `nightly`: some build before
+    // the release, taken to be the earliest kind.
+    Unnamed,
+    Alpha,
+    Beta,
+    Rc,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Kind {
+    // "Unknown", empty, or nothing we could read: older than anything.
+    Unknown,
+    // Month-name dates such as `Jan_12_2026` are read as year.month.day,
+    // so they order like calendar versions among numbered ones.
+    Numbered {
+        // Numeric core with trailing zeros dropped, so `2.6` == `2.6.0`.
+        core: Vec<u64>,
+        // `None` is a final release and sorts after every pre-release.
+        pre: PreRelease,
+        // `2.6.2_hotfix` is newer than `2.6.2`; a numbered hotfix beats an
+        // unnumbered one. Applies on top of the pre-release stage, so
+        // `2.6.2-beta_hotfix` sits between `2.6.2-beta` and `2.6.2-rc`.
+        hotfix: Option<u64>,
+        // Unrecognised words, compared last so the ordering stays total.
+        // Only single letters, as in `2.6.2a`, mark a revision after the
+        // release; longer words make the version an `Unnamed` pre-release.
+        other: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PreRelease(Option<(Stage, u64)>);
+
+impl Ord for PreRelease {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.0, other.0) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a.cmp(&b),
+        }
+    }
+}
+
+impl PartialOrd for PreRelease {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// A mod version as found in catalogs, release tags and zip names:
+// semver-ish numbers with alpha/beta/rc/hotfix suffixes, or dates.
+#[derive(Clone, Debug)]
+pub struct Version {
+    raw: String,
+    kind: Kind,
+}
+
+const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+fn month_number(word: &str) -> Option<u32> {
+    MONTHS.iter().position(|m| word.starts_with(m)).map(|i| i as u32 + 1)
+}
+
+// Split on separators and between letters and digits: `2.0-rc1` ->
+// [2, 0, rc, 1].
+fn tokens(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for part in input.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut current = String::new();
+        for c in part.chars() {
+            if !current.is_empty() && current.ends_with(|p: char| p.is_ascii_digit()) != c.is_ascii_digit() {
+                tokens.push(std::mem::take(&mut current));
+            }
+            current.push(c);
+        }
+        if !current.is_empty() {
+            tokens.push(current);
+        }
+    }
+    tokens
+}
+
+fn parse_date(tokens: &[String]) -> Option<Kind> {
+    let [month, day, year] = tokens else {
+        return None;
+    };
+    let month = month_number(month)?;
+    let day: u64 = day.parse().ok().filter(|d| (1..=31).contains(d))?;
+    let year: u64 = year.parse().ok().filter(|_| year.len() == 4)?;
+    Some(Kind::Numbered {
+        core: vec![year, u64::from(month), day],
+        pre: PreRelease(None),
+        hotfix: None,
+        other: Vec::new(),
+    })
+}
+
+fn parse(raw: &str) -> Kind {
+    let lower = raw.trim().to_lowercase();
+    if lower.is_empty() || lower == "unknown" {
+        return Kind::Unknown;
+    }
+    let tokens = tokens(lower.strip_prefix('v').filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit())).unwrap_or(&lower));
+    if let Some(date) = parse_date(&tokens) {
+        return date;
+    }
+
+    let mut core = Vec::new();
+    let mut rest = tokens.iter().peekable();
+    while let Some(n) = rest.peek().and_then(|t| t.parse::<u64>().ok()) {
+        core.push(n);
+        rest.next();
+    }
+    while core.last() == Some(&0) {
+        core.pop();
+    }
+
+    let mut pre = None;
+    let mut unnamed = None;
+    let mut hotfix = None;
+    let mut other = Vec::new();
+    while let Some(token) = rest.next() {
+        let number = rest.peek().and_then(|t| t.parse::<u64>().ok());
+        let stage = match token.as_str() {
+            "alpha" => Some(Stage::Alpha),
+            "beta" => Some(Stage::Beta),
+            // A lone letter is only a stage when numbered, as in `2.0b3`;
+            // `2.6.2a` is a revision after 2.6.2.
+            "a" if number.is_some() => Some(Stage::Alpha),
+            "b" if number.is_some() => Some(Stage::Beta),
+            "rc" | "pre" => Some(Stage::Rc),
+            _ => None,
+        };
+        if let Some(stage) = stage.filter(|_| pre.is_none()) {
+            pre = Some((stage, number.unwrap_or(0)));
+        } else if matches!(token.as_str(), "hotfix" | "hf") && hotfix.is_none() {
+            hotfix = Some(number.unwrap_or(0));
+        } else if token.len() > 1 && token.starts_with(|c: char| c.is_ascii_alphabetic()) && unnamed.is_none() {
+            unnamed = Some(number.unwrap_or(0));
+            other.push(token.clone());
+        } else {
+            other.push(token.clone());
+            continue;
+        }
+        if number.is_some() {
+            rest.next();
+        }
+    }
+
+    if core.is_empty() && pre.is_none() && hotfix.is_none() && !tokens.iter().any(|t| t.starts_with(|c: char| c.is_ascii_digit())) {
+        return Kind::Unknown;
+    }
+    Kind::Numbered {
+        core,
+        pre: PreRelease(pre.or(unnamed.map(|n| (Stage::Unnamed, n)))),
+        hotfix,
+        other,
+    }
+}
+
+impl Version {
+    pub fn parse(raw: &str) -> Version {
+        Version {
+            raw: raw.to_string(),
+            kind: parse(raw),
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.kind == Kind::Unknown
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.kind.cmp(&other.kind)
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+// Version constraint such as `>=2.6`, `>=2.6, <3` or `=2.6.2_hotfix`.
+// A bare version means "at least this version".
+#[derive(Clone, Debug)]
+pub struct VersionReq {
+    raw: String,
+    comparators: Vec<(Op, Version)>,
+}
+
+impl VersionReq {
+    pub fn parse(raw: &str) -> Result<VersionReq, String> {
+        let mut comparators = Vec::new();
+        for part in raw.split(',').map(str::trim).filter(|p| !p.is_empty() && *p != "*") {
+            let (op, rest) = [(">=", Op::Ge), ("<=", Op::Le), ("==", Op::Eq), (">", Op::Gt), ("<", Op::Lt), ("=", Op::Eq)]
+                .into_iter()
+                .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|rest| (op, rest)))
+                .unwrap_or((Op::Ge, part));
+            let version = Version::parse(rest.trim());
+            if version.is_unknown() {
+                return Err(format!("Invalid version constraint {:?}", raw));
+            }
+            comparators.push((op, version));
+        }
+        Ok(VersionReq {
+            raw: raw.trim().to_string(),
+            comparators,
+        })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|(op, bound)| {
+            !version.is_unknown()
+                && match op {
+                    Op::Eq => version == bound,
+                    Op::Gt => version > bound,
+                    Op::Ge => version >= bound,
+                    Op::Lt => version < bound,
+                    Op::Le => version <= bound,
+                }
+        })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn v(raw: &str) -> Version {
+        Version::parse(raw)
+    }
+
+    // Version strings in the shapes mods actually use, plus noise.
+    fn version_string() -> impl Strategy<Value = String> {
+        prop_oneof![
+            "v?[0-9]{1,2}([._][0-9]{1,2}){0,3}([._-]?(alpha|beta|rc|pre|a|b|hotfix|hf|x)[._-]?[0-9]{0,2}){0,2}",
+            "(jan|feb|mar|oct|dec)_[0-9]{1,2}_20[0-9]{2}",
+            "(alpha|beta|rc)_[0-9]{1,2}",
+            "(unknown|Unknown|latest|)",
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn ordering_is_antisymmetric(a in version_string(), b in version_string()) {
+            let (a, b) = (v(&a), v(&b));
+            prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+            prop_assert_eq!(a == b, a.cmp(&b) == Ordering::Equal);
+        }
+
+        #[test]
+        fn ordering_is_transitive(a in version_string(), b in version_string(), c in version_string()) {
+            let (a, b, c) = (v(&a), v(&b), v(&c));
+            if a <= b && b <= c {
+                prop_assert!(a <= c, "{} <= {} <= {} but not {} <= {}", a, b, c, a, c);
+            }
+            if a == b && b == c {
+                prop_assert!(a == c);
+            }
+        }
+
+        #[test]
+        fn sorting_gives_a_total_order(raws in proptest::collection::vec(version_string(), 2..12)) {
+            let mut versions: Vec<Version> = raws.iter().map(|raw| v(raw)).collect();
+            versions.sort();
+            for (i, earlier) in versions.iter().enumerate() {
+                for later in &versions[i + 1..] {
+                    prop_assert!(earlier <= later, "{} sorted before {}", earlier, later);
+                }
+            }
+        }
+
+        #[test]
+        fn unknown_suffixes_precede_the_release(
+            core in "[0-9]{1,2}([.][0-9]{1,2}){0,2}",
+            separator in "[._-]?",
+            word in "[a-z]{2,8}",
+        ) {
+            prop_assume!(!["alpha", "beta", "rc", "pre", "hotfix", "hf"].contains(&word.as_str()));
+            let suffixed = format!("{}{}{}", core, separator, word);
+            prop_assert!(v(&suffixed) < v(&core), "{} is not before {}", suffixed, core);
+        }
+
+        #[test]
+        fn parsing_is_reflexive(raw in version_string()) {
+            prop_assert_eq!(v(&raw).cmp(&v(&raw)), Ordering::Equal);
+        }
+    }
+
+    #[test]
+    fn numbers_compare_numerically() {
+        assert!(v("2.6.10") > v("2.6.2"));
+        assert!(v("10.0") > v("9.9.9"));
+        assert_eq!(v("2.6"), v("2.6.0"));
+        assert_eq!(v("v2.6.2"), v("2.6.2"));
+    }
+
+    #[test]
+    fn hotfixes_follow_their_release() {
+        assert!(v("2.6.2_hotfix") > v("2.6.2"));
+        assert!(v("2.6.2_hotfix") < v("2.6.3"));
+        assert!(v("2.6.2_hotfix2") > v("2.6.2_hotfix"));
+        assert!(v("2.6.2-beta_hotfix") > v("2.6.2-beta"));
+        assert!(v("2.6.2-beta_hotfix") < v("2.6.2-rc"));
+    }
+
+    #[test]
+    fn pre_releases_precede_the_release() {
+        assert!(v("2.7.0-alpha") < v("2.7.0-beta"));
+        assert!(v("2.7.0-beta.2") > v("2.7.0-beta.1"));
+        assert!(v("2.7.0-rc1") > v("2.7.0-beta.9"));
+        assert!(v("2.7.0-rc1") < v("2.7.0"));
+        assert!(v("2.0b3") < v("2.0"));
+        assert!(v("beta_3") > v("beta_2"));
+        assert!(v("beta_3") < v("rc_1"));
+    }
+
+    #[test]
+    fn trailing_letters_are_revisions() {
+        assert!(v("2.6.2a") > v("2.6.2"));
+        assert!(v("2.6.2b") > v("2.6.2a"));
+        assert!(v("2.6.2a") < v("2.6.3"));
+    }
+
+    #[test]
+    fn unknown_words_are_pre_releases() {
+        assert!(v("2.7.0-dev") < v("2.7.0"));
+        assert!(v("2.7.0-nightly") < v("2.7.0-alpha"));
+        assert!(v("2.7.0-dev2") > v("2.7.0-dev1"));
+        assert!(v("2.7.0-dev") > v("2.6.9"));
+        assert!(v("latest").is_unknown());
+    }
+
+    #[test]
+    fn dates_order_as_calendar_versions() {
+        assert!(v("Jan_12_2026") > v("Dec_31_2025"));
+        assert!(v("Mar_1_2026") > v("Feb_28_2026"));
+        assert!(v("Jan_12_2026") > v("2.6.2"));
+        assert!(v("Jan_12_2026") < v("2026.2"));
+    }
+
+    #[test]
+    fn unknown_is_oldest() {
+        assert!(v("Unknown").is_unknown());
+        assert!(v("").is_unknown());
+        assert!(v("Unknown") < v("0.1"));
+        assert!(v("Unknown") < v("beta_1"));
+    }
+
+    #[test]
+    fn requirements() {
+        let req = VersionReq::parse(">=2.6, <3").unwrap();
+        assert!(req.matches(&v("2.6.2_hotfix")));
+        assert!(!req.matches(&v("3.0")));
+        assert!(!req.matches(&v("Unknown")));
+        assert!(VersionReq::parse("2.6").unwrap().matches(&v("2.7")));
+        assert!(VersionReq::parse(">=nonsense").is_err());
+    }
+}
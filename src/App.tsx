@@ -1,28 +1,61 @@
 import { useState, useEffect } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { open } from "@tauri-apps/plugin-dialog";
 import "./App.css";
 import {
   Toast,
   ModConfig,
   InstalledMod,
   ModManifest,
+  Manifest,
+  LoadedManifest,
   UpdateInfo,
-  BackendConfig,
+  AvailableVersion,
+  LoadedCatalog,
+  CatalogSource,
+  InstallResult,
+  ReleaseLookup,
   DownloadProgressEventPayload,
   TabId,
   DownloadProgressMap,
+  DeleteOutcome,
+  ArchivedVersion,
+  Reconciliation,
+  Changelog,
+  Channel,
+  InstallableVersion,
+  ModpackImport,
+  ModpackResult,
+  ProfileStore,
+  SwitchReport,
+  SaveBackup,
+  ModsBackup,
+  ModsRestorePreview,
+  ModsRestoreReport,
+  GameCacheInfo,
+  CacheClearReport,
 } from "./types";
-import { isNewerVersion } from "./utils/version";
 import { Sidebar } from "./components/Sidebar";
 import { HeaderBar } from "./components/HeaderBar";
 import { Notifications } from "./components/Notifications";
 import { BrowseView } from "./components/BrowseView";
 import { SettingsPopover } from "./components/SettingsPopover";
 import { DeleteConfirmation } from "./components/DeleteConfirmation";
+import { ChangelogDialog } from "./components/ChangelogDialog";
+import { VersionPickerDialog } from "./components/VersionPickerDialog";
+import { ModpackImportDialog } from "./components/ModpackImportDialog";
+import { SaveBackupsPanel } from "./components/SaveBackupsPanel";
+import { ModsBackupsPanel } from "./components/ModsBackupsPanel";
+import { ModsRestoreDialog } from "./components/ModsRestoreDialog";
 import { InstalledModsTable } from "./components/InstalledModsTable";
-import { cn } from "./utils/cn";
+import { formatBytes } from "./utils/formatBytes";
+
+// Human-readable age of a cached catalog
+const formatAge = (seconds: number) => {
+    if (seconds < 3600) return `${Math.max(1, Math.round(seconds / 60))} min`;
+    if (seconds < 86400) return `${Math.round(seconds / 3600)} h`;
+    return `${Math.round(seconds / 86400)} days`;
+};
 
 // Helper to identify mods from Post Titles/Slugs
 
@@ -33,13 +66,33 @@ function App() {
   const [manifest, setManifest] = useState<ModManifest>({});
   const [status, setStatus] = useState<string>("Initializing...");
   const [updates, setUpdates] = useState<UpdateInfo[]>([]);
+  const [catalogUpdates, setCatalogUpdates] = useState<UpdateInfo[]>([]);
   const [toasts, setToasts] = useState<Toast[]>([]);
   const [installingMods, setInstallingMods] = useState<{ [modId: string]: boolean }>({});
   const [modsConfig, setModsConfig] = useState<ModConfig[]>([]);
   const [showSettingsPopover, setShowSettingsPopover] = useState<boolean>(false);
-  const [manifestFilePath, setManifestFilePath] = useState<string | null>(null);
   const [pendingDelete, setPendingDelete] = useState<{ modId: string; filename: string } | null>(null);
   const [downloadProgress, setDownloadProgress] = useState<DownloadProgressMap>({});
+  const [archivedVersions, setArchivedVersions] = useState<ArchivedVersion[]>([]);
+  const [catalogSources, setCatalogSources] = useState<CatalogSource[]>([]);
+  const [hasGithubToken, setHasGithubToken] = useState<boolean>(false);
+  const [versionPickerModId, setVersionPickerModId] = useState<string | null>(null);
+  const [installableVersions, setInstallableVersions] = useState<InstallableVersion[] | null>(null);
+  const [versionPickerError, setVersionPickerError] = useState<string | null>(null);
+  const [modpackImport, setModpackImport] = useState<ModpackImport | null>(null);
+  const [applyingModpack, setApplyingModpack] = useState<boolean>(false);
+  const [gameCache, setGameCache] = useState<GameCacheInfo | null>(null);
+  const [saveBackups, setSaveBackups] = useState<SaveBackup[]>([]);
+  const [saveBackupBusy, setSaveBackupBusy] = useState<boolean>(false);
+  const [modsBackups, setModsBackups] = useState<ModsBackup[]>([]);
+  const [modsBackupBusy, setModsBackupBusy] = useState<boolean>(false);
+  const [modsRestorePreview, setModsRestorePreview] = useState<ModsRestorePreview | null>(null);
+  const [profiles, setProfiles] = useState<ProfileStore>({ profiles: [] });
+  const [switchingProfile, setSwitchingProfile] = useState<boolean>(false);
+  const [modChannels, setModChannels] = useState<Record<string, Channel>>({});
+  const [changelogModName, setChangelogModName] = useState<string | null>(null);
+  const [changelog, setChangelog] = useState<Changelog | null>(null);
+  const [changelogError, setChangelogError] = useState<string | null>(null);
 
     // Helper: find installed entry for a mod either by id or by filename pattern
     const getInstalledForMod = (mod: ModConfig): InstalledMod | null => {
@@ -72,7 +125,92 @@ function App() {
     const removeToast = (id: number) => {
             setToasts(prev => prev.filter(t => t.id !== id));
     };
+
+    // Move a file from the mods folder to the installer trash and throw unless it is
+    // really gone, so callers never drop a manifest entry for a zip that is still on disk.
+    const deleteModFile = async (
+        filePath: string,
+        owner?: { modId: string; version?: string },
+        options?: { archive?: boolean; scheduleIfLocked?: boolean }
+    ) => {
+        const outcome = await invoke<DeleteOutcome>("delete_old_mod", {
+            filePath,
+            modId: owner?.modId,
+            version: owner?.version,
+            mode: options?.archive ? "archive" : "trash",
+            scheduleIfLocked: options?.scheduleIfLocked ?? false,
+        });
+        if (outcome.status === "locked") {
+            throw new Error(
+                outcome.scheduled
+                    ? `${filePath} is in use and will be removed the next time RLS Installer starts`
+                    : `${filePath} is in use by another program (${outcome.error})`
+            );
+        }
+        if (outcome.status === "failed") {
+            throw new Error(`Could not delete ${filePath}: ${outcome.error}`);
+        }
+        return outcome;
+    };
    
+    const loadManifest = async (): Promise<ModManifest> => {
+        const loaded = await invoke<LoadedManifest>("load_manifest");
+        if (loaded.recovered_from === "empty") {
+            addToast("Your mod list was damaged and no backup could be read, so it has been reset. The damaged file was kept as mod_manifest.json.corrupt.", 'error');
+        } else if (loaded.recovered_from) {
+            addToast("Your mod list was damaged and has been restored from a backup.", 'info');
+        }
+        return loaded.mods;
+    };
+
+    const loadCatalog = async (): Promise<ModConfig[]> => {
+        const catalog = await invoke<LoadedCatalog>("load_catalog");
+        catalog.sources.forEach((source) => {
+            console.log(`[CATALOG] ${source.id}: ${source.entries} mods from ${source.loaded_from ?? "nowhere"}`, source.location);
+            const label = source.name || source.id;
+            if (source.loaded_from === null) {
+                addToast(`Mod catalog "${label}" could not be loaded: ${source.error}`, 'error');
+            } else if (source.loaded_from === "cached") {
+                const age = source.age_secs != null ? ` from ${formatAge(source.age_secs)} ago` : "";
+                addToast(`Mod catalog "${label}" is offline, using the copy${age}.`, 'info');
+            } else if (source.loaded_from === "bundled" && source.error) {
+                // Without a configured URL the bundled catalog is the source itself.
+                addToast(`Mod catalog "${label}" is offline, using the one bundled with the installer.`, 'info');
+            }
+        });
+        catalog.issues.forEach((issue) => {
+            console.warn(
+                `[CATALOG] ${issue.source} entry ${issue.index}${issue.mod_id ? ` (${issue.mod_id})` : ""}` +
+                    `${issue.field ? ` ${issue.field}` : ""}: ${issue.message}`
+            );
+        });
+        const skipped = catalog.issues.filter((issue) => issue.severity === "error").length;
+        if (skipped > 0) {
+            addToast(`${skipped} problem(s) in the mod catalog; affected mods are hidden.`, 'error');
+        }
+        return catalog.mods;
+    };
+
+    const reconcileManifest = async (catalog: ModConfig[]): Promise<Reconciliation> => {
+        const result = await invoke<Reconciliation>("reconcile_mods", { catalog, apply: true });
+        const { diff } = result;
+        if (diff.added.length || diff.removed.length || diff.version_changed.length) {
+            console.log("[RESCAN] Manifest updated from mods folder:", diff);
+        }
+        diff.ambiguous.forEach((m) => {
+            console.warn(`[RESCAN] ${m.filename} matches ${m.candidates.join(", ")}, tracked as ${m.assigned_to}`);
+        });
+        return result;
+    };
+
+    const refreshArchivedVersions = async () => {
+        try {
+            setArchivedVersions(await invoke<ArchivedVersion[]>("list_archived_versions"));
+        } catch (e) {
+            console.error("Failed to list archived versions:", e);
+        }
+    };
+
   // --- Initialization ---
 
     const rescanInstalledMods = async () => {
@@ -85,122 +223,10 @@ function App() {
         console.log("[RESCAN] Current manifest:", manifest);
 
         try {
-            // 1. Load current manifest from the per-user appdata folder
-            let currentManifest: ModManifest = {};
-            try {
-                const manifestPath = manifestFilePath;
-                if (!manifestPath) {
-                    throw new Error("manifestFilePath not set");
-                }
-                console.log("[RESCAN] Loading manifest from:", manifestPath);
-                const content = await invoke<string>("read_manifest", { path: manifestPath });
-                currentManifest = JSON.parse(content);
-                console.log("[RESCAN] Loaded manifest:", currentManifest);
-            } catch (e) {
-                console.log("[RESCAN] No manifest found during rescan.");
-            }
-
-            // 2. Scan directory for actual files
-            const files = await invoke<string[]>("scan_mods_folder", { path: beamUserPath });
-            console.log("[RESCAN] Found files on disk:", files);
-
-            // 3. Remove manifest entries for files that no longer exist
-            const updatedManifest = { ...currentManifest };
-            let changes = false;
-
-            // Remove entries where file doesn't exist anymore
-            Object.keys(updatedManifest).forEach(modId => {
-                const filename = updatedManifest[modId].filename;
-                if (!files.includes(filename)) {
-                    console.log(`[RESCAN] Removing ${modId} - file ${filename} no longer exists`);
-                    delete updatedManifest[modId];
-                    changes = true;
-                }
-            });
-
-            // Extract version helper (same as in init)
-            const extractVersion = (fname: string) => {
-                const name = fname.replace(/\.zip$/i, "");
-                // Hotfix pattern: e.g. rls_career_overhaul_2.6.2_hotfix
-                const hotfixMatch = name.match(/_(\d+(?:\.\d+)+_hotfix)\s*$/i);
-                if (hotfixMatch) return hotfixMatch[1];
-
-                const complexMatch = name.match(/_(\d+\.\d+(?:[._-](?:beta|alpha|rc)?[._-]?\d+)?)\s*$/i);
-                if (complexMatch) return complexMatch[1].replace(/_/g, ".");
-                const vMatch = name.match(/v(\d+(?:[._-]\d+)*)/i);
-                if (vMatch) return vMatch[1];
-                const semVerMatch = name.match(/(\d+\.\d+(?:[._]\d+)*)$/);
-                if (semVerMatch) return semVerMatch[0];
-                const phaseMatch = name.match(/(beta|alpha|rc)[_-]?(\d+)/i);
-                if (phaseMatch) return `${phaseMatch[1]} ${phaseMatch[2]}`;
-                const dateMatch = name.match(/([a-zA-Z]+_\d{1,2}_\d{4})/);
-                if (dateMatch) return dateMatch[0];
-                const parts = name.split(/[_-]/);
-                const lastPart = parts[parts.length - 1];
-                if (/\d/.test(lastPart) && lastPart.length < 15) return lastPart;
-                return null;
-            };
-
-            // Add entries for new files
-            files.forEach(filename => {
-                const existingModId = Object.keys(updatedManifest).find(
-                    key => updatedManifest[key].filename === filename
-                );
-                
-                if (!existingModId) {
-                    // New file found - try to match to known mod or create new entry
-                    const version = extractVersion(filename) || "Unknown";
-                    
-                    // Try to match with configured mods
-                    const sortedMods = [...modsConfig].sort((a, b) => 
-                        (b.assetPattern?.length || 0) - (a.assetPattern?.length || 0)
-                    );
-                    
-                    let matchedMod = null;
-                    for (const mod of sortedMods) {
-                        if (mod.assetPattern) {
-                            const regex = new RegExp(mod.assetPattern, 'i');
-                            if (regex.test(filename)) {
-                                matchedMod = mod;
-                                break;
-                            }
-                        }
-                    }
-                    
-                    const id = matchedMod ? matchedMod.id : filename;
-                    console.log(`[RESCAN] Adding new file ${filename} as ${id}`);
-                    
-                    updatedManifest[id] = {
-                        version: version,
-                        filename: filename
-                    };
-                    changes = true;
-                }
-            });
-
-            if (changes) {
-                console.log("[RESCAN] ===== MANIFEST CHANGED =====");
-                console.log("[RESCAN] Old manifest had", Object.keys(currentManifest).length, "entries");
-                console.log("[RESCAN] New manifest has", Object.keys(updatedManifest).length, "entries");
-                console.log("[RESCAN] Updated manifest:", updatedManifest);
-                
-                setManifest(updatedManifest);
-                console.log("[RESCAN] Called setManifest with:", updatedManifest);
-                
-                // Save updated manifest into the per-user appdata folder
-                if (manifestFilePath) {
-                    await invoke("save_manifest", {
-                        path: manifestFilePath,
-                        content: JSON.stringify(updatedManifest, null, 2)
-                    });
-                    console.log("[RESCAN] Saved manifest to disk");
-                } else {
-                    console.error("[RESCAN] manifestFilePath not set, cannot save manifest");
-                }
-            } else {
-                console.log("[RESCAN] No changes detected - manifest is up to date");
-                console.log("[RESCAN] Current manifest:", currentManifest);
-            }
+            // Match the manifest against the zips on disk and save the result
+            const result = await reconcileManifest(modsConfig);
+            setManifest(result.manifest.mods);
+            await refreshArchivedVersions();
             console.log("[RESCAN] ===== RESCAN COMPLETE =====");
         } catch (err) {
             console.error("[RESCAN] Error during rescan:", err);
@@ -211,56 +237,34 @@ function App() {
       console.error("========== INIT STARTING ==========");
       console.log("Init function called at:", new Date().toISOString());
       try {
-        // 0. Load backend config only for remote mods.json URL (no Patreon auth)
-        let remoteMogsJsonUrl = "";
-        try {
-            const config = await invoke<BackendConfig>("get_patreon_config");
-            console.log("Loaded config from backend:", config);
-            if (config.remote_mods_json_url) {
-                remoteMogsJsonUrl = config.remote_mods_json_url;
-            }
-        } catch (e) {
-            console.warn("Failed to load backend config, using defaults:", e);
-        }
-        
-        // 1. Load mod catalog from remote URL (updatable without app rebuild)
-        // NOTE: With creator token, this is only used as fallback if API fetch fails
+        // 0. Load the mod catalog: remote, else the last cached copy, else the bundled one
         let staticCatalog: ModConfig[] = [];
         try {
-            // Try remote first, fallback to bundled
-            let response;
-            try {
-                if (remoteMogsJsonUrl) {
-                    response = await fetch(remoteMogsJsonUrl);
-                    console.log(`Loaded mods from remote: ${remoteMogsJsonUrl}`);
-                } else {
-                    throw new Error("No remote URL configured");
-                }
-            } catch (e) {
-                console.warn("Failed to load remote mods.json, using bundled fallback:", e);
-                response = await fetch('/mods.json');
-            }
-            staticCatalog = await response.json();
-            console.log(`Loaded ${staticCatalog.length} mods from catalog (fallback only)`);
+            staticCatalog = await loadCatalog();
         } catch (e) {
             console.error("Failed to load mods.json:", e);
         }
 
-
         const loadedConfig: ModConfig[] = staticCatalog;
         setModsConfig(staticCatalog);
-        
-        const path = await invoke<string>("detect_beamng_user_path");
-        setBeamUserPath(path);
-
-        // Per-user manifest path in RLS Installer app data
-        let userManifestPath: string | null = null;
         try {
-            userManifestPath = await invoke<string>("get_manifest_path");
-            setManifestFilePath(userManifestPath);
+            setProfiles(await invoke<ProfileStore>("list_profiles"));
+            setSaveBackups(await invoke<SaveBackup[]>("list_save_backups"));
+            setModsBackups(await invoke<ModsBackup[]>("list_mods_backups"));
         } catch (e) {
-            console.error("Failed to resolve per-user manifest path:", e);
+            console.error("Failed to load profiles and backups:", e);
         }
+        try {
+            setModChannels(await invoke<Record<string, Channel>>("get_mod_channels"));
+        } catch (e) {
+            console.error("Failed to load release channels:", e);
+        }
+        
+        // Folder chosen in settings, or the detected default
+        const path = await invoke<string>("get_mods_folder");
+        setBeamUserPath(path);
+
+        // Manifests left in older locations are migrated by the backend at startup.
         
         // Ensure status reflects we are done initializing (Errors are now in Toasts)
         setStatus("Ready");
@@ -268,152 +272,16 @@ function App() {
         // 1. Load Manifests
         let currentManifest: ModManifest = {};
 
-        if (userManifestPath) {
-            try {
-                const content = await invoke<string>("read_manifest", { path: userManifestPath });
-                currentManifest = JSON.parse(content);
-            } catch (e) {
-                console.log("No manifest found or error reading it.");
-            }
+        try {
+            currentManifest = await loadManifest();
+        } catch (e) {
+            console.log("No manifest found or error reading it.", e);
         }
 
-        // 2. Scan Directory for Zip files to find unmanaged mods
+        // 2. Reconcile with the zips in the mods folder to pick up unmanaged mods
         try {
-            const files = await invoke<string[]>("scan_mods_folder", { path });
-            console.log("Scanned files:", files);
-
-            // Reconcile with manifest
-            const updatedManifest = { ...currentManifest };
-            let changes = false;
-
-            const extractVersion = (fname: string) => {
-                const name = fname.replace(/\.zip$/i, "");
-                
-                // 0. Hotfix pattern: e.g. rls_career_overhaul_2.6.2_hotfix
-                const hotfixMatch = name.match(/_(\d+(?:\.\d+)+_hotfix)\s*$/i);
-                if (hotfixMatch) return hotfixMatch[1];
-                
-                // 1. Complex Version ending (e.g., 2.0_beta_2)
-                const complexMatch = name.match(/_(\d+\.\d+(?:[._-](?:beta|alpha|rc)?[._-]?\d+)?)\s*$/i);
-                if (complexMatch) return complexMatch[1].replace(/_/g, ".");
-
-                // 2. Explicit version pattern vX.X.X (capture only numbers/dots/dashes)
-                const vMatch = name.match(/v(\d+(?:[._-]\d+)*)/i);
-                if (vMatch) return vMatch[1]; 
-
-
-                // 2. Standard SemVer-ish pattern X.X.X (at least one dot)
-                const semVerMatch = name.match(/(\d+\.\d+(?:[._]\d+)*)$/);
-                if (semVerMatch) return semVerMatch[0];
-
-                // 3. Beta/Alpha pattern (e.g. beta_5)
-                const phaseMatch = name.match(/(beta|alpha|rc)[_-]?(\d+)/i);
-                if (phaseMatch) return `${phaseMatch[1]}_${phaseMatch[2]}`;
-
-                // 4. Date pattern like Jan_12_2026
-                const dateMatch = name.match(/([a-zA-Z]+_\d{1,2}_\d{4})/);
-                if (dateMatch) return dateMatch[0];
-
-                // 5. Fallback: Last segment matches digits
-                const parts = name.split(/[_-]/);
-                const lastPart = parts[parts.length - 1];
-                if (/\d/.test(lastPart) && lastPart.length < 15) {
-                    return lastPart;
-                }
-                
-                return null;
-            }
-
-            files.forEach(filename => {
-                const version = extractVersion(filename) || "Unknown";
-                console.log(`[VERSION EXTRACTION] File: ${filename} -> Version: ${version}`);
-                
-                // Check if this file is already associated with a Mod ID in the manifest
-                const existingModId = Object.keys(updatedManifest).find(key => updatedManifest[key].filename === filename);
-
-                // Helper to score version quality (higher is better)
-                const getVersionScore = (v: string) => {
-                    if (v === "Unknown") return 0;
-                    if (v.includes(".")) return 3; // SemVer
-                    if (v.toLowerCase().includes("beta") || v.toLowerCase().includes("alpha")) return 2; // Clearly marked phase
-                    if (/\d{4}/.test(v)) return 2; // Date likely
-                    return 1; // Just a number
-                };
-
-                if (existingModId) {
-                     const entry = updatedManifest[existingModId];
-                     
-                     // Always update version from filename - filename is source of truth
-                     if (entry.version !== version) {
-                         updatedManifest[existingModId] = {
-                             ...entry,
-                             version: version
-                         };
-                         changes = true;
-                     }
-                } else {
-                     // New file found.
-                     // Try to match with known configuration
-                     // Use the locally loaded 'loadedConfig' variable which is fresh
-                     // Sort by pattern specificity - more specific patterns (longer) first
-                     const sortedConfig = [...loadedConfig].sort((a, b) => {
-                         const aLen = a.assetPattern?.length || 0;
-                         const bLen = b.assetPattern?.length || 0;
-                         return bLen - aLen; // Longer patterns first
-                     });
-                     
-                     const matchedMod = sortedConfig.find(mod => {
-                        if (mod.assetPattern) {
-                            try {
-                                const matches = new RegExp(mod.assetPattern, 'i').test(filename);
-                                console.log(`[MOD MATCHING] Testing ${filename} against ${mod.id} pattern ${mod.assetPattern}: ${matches}`);
-                                return matches;
-                            } catch (e) {
-                                console.error(`Invalid regex for mod ${mod.id}:`, e);
-                                return false; 
-                            }
-                        }
-                        // Fallback to simple inclusion if no pattern
-                        const matches = filename.toLowerCase().includes(mod.id.toLowerCase().replace("rls_", ""));
-                        console.log(`[MOD MATCHING] Testing ${filename} against ${mod.id} by name inclusion: ${matches}`);
-                        return matches;
-                     }); 
-                     
-                     const id = matchedMod ? matchedMod.id : filename;
-                     console.log(`[MOD ID ASSIGNMENT] File ${filename} -> Mod ID: ${id}, Version: ${version}`);
-
-                     if (updatedManifest[id]) {
-                         const currentVersion = updatedManifest[id].version;
-                         const currentFilename = updatedManifest[id].filename;
-                         const currentScore = getVersionScore(currentVersion);
-                         const newScore = getVersionScore(version);
-                         
-                         if (currentFilename !== filename) {
-                             if (newScore >= currentScore) {
-                                updatedManifest[id] = {
-                                    version: version,
-                                    filename: filename
-                                };
-                                changes = true;
-                             }
-                         }
-                     } else {
-                        updatedManifest[id] = {
-                            version: version,
-                            filename: filename
-                        };
-                        changes = true;
-                     }
-                }
-            });
-
-            if (changes) {
-                console.log("Updated manifest from scan:", updatedManifest);
-                setManifest(updatedManifest);
-            } else {
-                setManifest(currentManifest);
-            }
-
+            const result = await reconcileManifest(loadedConfig);
+            setManifest(result.manifest.mods);
         } catch (e) {
             console.error("Error scanning folder:", e);
             setManifest(currentManifest);
@@ -469,6 +337,16 @@ function App() {
         };
     }, [modsConfig]);
 
+  // Versions pinned in the catalog itself on each mod's release channel, compared
+  // against the manifest by the backend. Mods that are not installed are included
+  // so the cards install the build of the chosen channel.
+  useEffect(() => {
+    invoke<AvailableVersion[]>("channel_versions", { catalog: modsConfig })
+      .then((available) => invoke<UpdateInfo[]>("check_updates", { available, includeUninstalled: true }))
+      .then(setCatalogUpdates)
+      .catch((e) => console.error("Catalog update check failed:", e));
+  }, [manifest, modsConfig, modChannels]);
+
   // Rescan installed mods when switching to Installed tab
   useEffect(() => {
     console.log("[EFFECT] Active tab changed to:", activeTab, "beamUserPath:", beamUserPath);
@@ -481,53 +359,9 @@ function App() {
   const refreshModsJsonCatalog = async () => {
     try {
       setStatus("Refreshing mod catalog...");
-      let updatedCatalog: ModConfig[] = [];
-
-      // First, try to load from local repo (for development/testing)
-      try {
-        const localResponse = await fetch('/mods.json');
-        if (localResponse.ok) {
-          updatedCatalog = await localResponse.json();
-          console.log(`Loaded ${updatedCatalog.length} mods from local mods.json`);
-          setModsConfig(updatedCatalog);
-          return updatedCatalog;
-        }
-      } catch (e) {
-        console.warn("Failed to load local mods.json, trying GitHub...", e);
-      }
-
-      // Fall back to GitHub remote mods.json for released versions
-      try {
-        // Try to get URL from backend config, otherwise use a default GitHub URL
-        let remoteMogsJsonUrl = "";
-        try {
-          const config = await invoke<BackendConfig>("get_patreon_config");
-          if (config.remote_mods_json_url) {
-            remoteMogsJsonUrl = config.remote_mods_json_url;
-          }
-        } catch (e) {
-          console.warn("Failed to load backend config", e);
-        }
-
-        // If no URL from config, use default GitHub location
-        if (!remoteMogsJsonUrl) {
-          remoteMogsJsonUrl = "https://raw.githubusercontent.com/RLS-Modding/rls-installer/main/public/mods.json";
-        }
-
-        const remoteResponse = await fetch(remoteMogsJsonUrl);
-        if (remoteResponse.ok) {
-          updatedCatalog = await remoteResponse.json();
-          console.log(`Loaded ${updatedCatalog.length} mods from remote: ${remoteMogsJsonUrl}`);
-          setModsConfig(updatedCatalog);
-          return updatedCatalog;
-        } else {
-          throw new Error(`Failed to fetch remote mods.json: ${remoteResponse.status}`);
-        }
-      } catch (e) {
-        console.error("Failed to load remote mods.json from GitHub:", e);
-        addToast("Failed to refresh mod catalog. Using cached version.", 'error');
-        return modsConfig;
-      }
+      const updatedCatalog = await loadCatalog();
+      setModsConfig(updatedCatalog);
+      return updatedCatalog;
     } catch (e) {
       console.error("Error refreshing mods catalog:", e);
       addToast("Error refreshing mod catalog.", 'error');
@@ -544,67 +378,41 @@ function App() {
 
     setStatus("Checking for updates...");
     console.log("Detecting updates..."); // Debug log
-    const newUpdates: UpdateInfo[] = [];
-
-    // Use the refreshed mods catalog
-    for (const mod of updatedCatalog) {
-      if (mod.githubRepo) {
-        try {
-            const ghResponse = await fetch(`https://api.github.com/repos/${mod.githubRepo}/releases/latest`);
-            if (ghResponse.ok) {
-                const ghData = await ghResponse.json();
-                const ghVersion = ghData.tag_name; 
-                const installed = getInstalledForMod(mod);
-                
-                if (includeUninstalled) {
-                    // Card-level "Get Mod" can treat non-installed mods as available.
-                    if (!installed || isNewerVersion(ghVersion, installed.version)) {
-                        newUpdates.push({
-                            modId: mod.id,
-                            newVersion: ghVersion,
-                            downloadUrl: ghData.assets[0]?.browser_download_url
-                        });
-                    }
-                } else {
-                    // Global checks only care about already-installed mods being out of date.
-                    if (installed && isNewerVersion(ghVersion, installed.version)) {
-                        newUpdates.push({
-                            modId: mod.id,
-                            newVersion: ghVersion,
-                            downloadUrl: ghData.assets[0]?.browser_download_url
-                        });
-                    }
-                }
+    // Resolve the latest version of every mod, then let the backend decide what is outdated
+    const available: AvailableVersion[] = [];
+    try {
+        // GitHub releases are looked up by the backend (conditional requests, optional token)
+        const lookups = await invoke<ReleaseLookup[]>("resolve_github_releases", { catalog: updatedCatalog });
+        lookups.forEach((lookup) => {
+            if (lookup.release) {
+                available.push({
+                    modId: lookup.mod_id,
+                    version: lookup.release.version,
+                    downloadUrl: lookup.release.download_url
+                });
+            } else {
+                // Don't toast per mod failure, it's noisy. 
+                console.error(`GitHub check failed for ${lookup.mod_id}:`, lookup.error);
             }
-        } catch (e) {
-            console.error(`GitHub check failed for ${mod.name}`, e);
-            // Don't toast per mod failure, it's noisy. 
-        }
-      } else if (mod.version && mod.directDownload) {
-          // Handle generic public direct-link mods (e.g., GitHub assets, public file hosts)
-          const installedData = getInstalledForMod(mod);
-          const installedVersion = installedData?.version || "0.0.0";
-
-          if (includeUninstalled) {
-              // Card-level checks may install even if not yet present.
-              if (!installedData || isNewerVersion(mod.version, installedVersion)) {
-                  newUpdates.push({
-                      modId: mod.id,
-                      newVersion: mod.version,
-                      downloadUrl: mod.directDownload
-                  });
-              }
-          } else {
-              // Global checks only signal updates for already-installed mods.
-              if (installedData && isNewerVersion(mod.version, installedVersion)) {
-                  newUpdates.push({
-                      modId: mod.id,
-                      newVersion: mod.version,
-                      downloadUrl: mod.directDownload
-                  });
-              }
-          }
-      }
+        });
+    } catch (e) {
+        console.error("GitHub release lookup failed:", e);
+    }
+    try {
+        // Direct-link mods, on the build of each mod's release channel
+        const direct = await invoke<AvailableVersion[]>("channel_versions", { catalog: updatedCatalog });
+        available.push(...direct.filter((candidate) => candidate.downloadUrl));
+    } catch (e) {
+        console.error("Channel lookup failed:", e);
+    }
+
+    // Card-level "Get Mod" can treat non-installed mods as available; global checks
+    // only care about already-installed mods being out of date.
+    let newUpdates: UpdateInfo[] = [];
+    try {
+        newUpdates = await invoke<UpdateInfo[]>("check_updates", { available, includeUninstalled });
+    } catch (e) {
+        console.error("Update check failed:", e);
     }
 
     setUpdates(newUpdates);
@@ -624,7 +432,36 @@ function App() {
     }
   };
 
-  const installMod = async (update: UpdateInfo) => {
+  // Switch a mod between stable and beta builds and look for updates on the new channel
+  const setModChannel = async (mod: ModConfig, channel: Channel) => {
+      try {
+          setModChannels(await invoke<Record<string, Channel>>("set_mod_channel", { modId: mod.id, channel }));
+          addToast(`${mod.name} now follows the ${channel} channel`, 'success');
+          await detectUpdates({ silent: true });
+      } catch (err) {
+          console.error("Failed to change release channel:", err);
+          addToast(`Failed to change release channel: ${err}`, 'error');
+      }
+  };
+
+  const showChangelog = async (mod: ModConfig, update: UpdateInfo) => {
+    setChangelogModName(mod.name);
+    setChangelog(null);
+    setChangelogError(null);
+    try {
+      const result = await invoke<Changelog>("get_changelog", {
+        modId: mod.id,
+        fromVersion: update.installedVersion ?? getInstalledForMod(mod)?.version ?? null,
+        toVersion: update.newVersion,
+      });
+      setChangelog(result);
+    } catch (e) {
+      setChangelogError(`Could not load release notes: ${e}`);
+    }
+  };
+
+  // `pin` pins the mod at the installed version (or unpins it when false)
+  const installMod = async (update: UpdateInfo, options?: { pin?: boolean }) => {
       if (!beamUserPath) {
           addToast("Error: BeamNG detected path is missing.", 'error');
           return;
@@ -636,82 +473,36 @@ function App() {
       setStatus(`Downloading ${modConfig.name}...`);
 
       try {
-                    // Remove any existing installed file for this mod from the manifest
-                    const currentInstall = manifest[update.modId];
-                    if (currentInstall) {
-                        const oldPath = `${beamUserPath}\\${currentInstall.filename}`;
-                        await invoke("delete_old_mod", { filePath: oldPath });
-                    }
-
-                    // Additionally, remove any other zip files in the mods folder that match this mod's assetPattern
-                    // to avoid multiple versions of the same mod conflicting.
-                    if (modConfig.assetPattern) {
-                        try {
-                            const files = await invoke<string[]>("scan_mods_folder", { path: beamUserPath });
-                            const regex = new RegExp(modConfig.assetPattern, 'i');
-                            for (const file of files) {
-                                    if (regex.test(file)) {
-                                            const fullPath = `${beamUserPath}\\${file}`;
-                                            await invoke("delete_old_mod", { filePath: fullPath });
-                                    }
-                            }
-                        } catch (e) {
-                            console.error("Failed to clean up old versions for", modConfig.id, e);
-                        }
-                    }
-
-          // Initial guess for filename/path; backend may override this
-          // to match the original filename from the server.
-          let finalFilename: string;
-          let finalPath: string;
-
-          {
-              const urlParts = update.downloadUrl.split('/');
-              const guessedName = urlParts[urlParts.length - 1] || `${modConfig.id}_${update.newVersion || "latest"}.zip`;
-              finalFilename = guessedName.replace(/[<>:"/\\|?*]/g, "_");
-              finalPath = `${beamUserPath}\\${finalFilename}`;
-
-              const result = await invoke<string>("download_mod", { 
-                  url: update.downloadUrl, 
-                  targetPath: finalPath,
-                  modId: update.modId
-              });
-
-              // Try to use the real filename returned by the backend
-              try {
-                  const parsed = JSON.parse(result ?? "") as { path?: string; filename?: string };
-                  if (parsed && parsed.filename) {
-                      finalFilename = parsed.filename;
-                  }
-              } catch {
-                  // Ignore parse errors and keep the guessed filename
-              }
-          }
-
-          const newManifest = {
-              ...manifest,
-              [update.modId]: {
-                  version: update.newVersion,
-                  filename: finalFilename
-              }
-          };
-          
-          setManifest(newManifest);
-          if (manifestFilePath) {
-              await invoke("save_manifest", { 
-                  path: manifestFilePath, 
-                  content: JSON.stringify(newManifest, null, 2) 
-              });
-          } else {
-              console.error("[INSTALL] manifestFilePath not set, cannot save manifest");
+          // The backend installs missing dependencies first, archives the
+          // replaced version and refuses known conflicts before touching anything.
+          const result = await invoke<InstallResult>("install_mod", {
+              modId: update.modId,
+              version: update.newVersion,
+              downloadUrl: update.downloadUrl,
+              pin: options?.pin ?? null,
+          });
+          setManifest(result.manifest.mods);
+          if (result.cache_cleared) {
+              addToast(`Cleared ${formatBytes(result.cache_cleared.freed_bytes)} of BeamNG cache`, 'info');
           }
 
-          setUpdates(prev => prev.filter(u => u.modId !== update.modId));
-          addToast(`Installed ${modConfig.name}`, 'success');
+          const installedIds = result.plan.steps.map(step => step.mod_id);
+          setUpdates(prev => prev.filter(u => !installedIds.includes(u.modId)));
+          const dependencies = result.plan.steps
+              .filter(step => step.reason.kind === "dependency")
+              .map(step => step.name);
+          addToast(
+              dependencies.length > 0
+                  ? `Installed ${modConfig.name} with ${dependencies.join(", ")}`
+                  : `Installed ${modConfig.name}`,
+              'success'
+          );
           setStatus("Ready");
           
           // Rescan to update installed mods list
           await rescanInstalledMods();
+          // Updating a core mod backs up the career saves first
+          await refreshSaveBackups();
       } catch (err) {
           const message = String(err ?? 'Unknown error');
           {
@@ -727,6 +518,70 @@ function App() {
       }
   };
 
+  // Install every outdated mod in turn; pinned mods are never offered by check_updates
+  const updateAll = async (pending: UpdateInfo[]) => {
+      for (const update of pending) {
+          if (manifest[update.modId]?.pinned) continue;
+          await installMod(update);
+      }
+  };
+
+  const setModPinned = async (modId: string, pinned: boolean) => {
+      try {
+          const result = await invoke<Manifest>("set_mod_pinned", { modId, pinned });
+          setManifest(result.mods);
+          if (pinned) setUpdates(prev => prev.filter(u => u.modId !== modId));
+          addToast(pinned ? `Pinned ${modId} at ${result.mods[modId]?.version}` : `Unpinned ${modId}`, 'success');
+      } catch (err) {
+          addToast(`Failed to change pin: ${err}`, 'error');
+      }
+  };
+
+  const openVersionPicker = async (modId: string) => {
+      setVersionPickerModId(modId);
+      setInstallableVersions(null);
+      setVersionPickerError(null);
+      try {
+          setInstallableVersions(await invoke<InstallableVersion[]>("list_mod_versions", { modId }));
+      } catch (err) {
+          setVersionPickerError(`Could not list versions: ${err}`);
+      }
+  };
+
+  // A version picked by hand stays installed: it is pinned until the user unpins it
+  const installSpecificVersion = async (modId: string, version: InstallableVersion) => {
+      setVersionPickerModId(null);
+      await installMod(
+          { modId, newVersion: version.version, downloadUrl: version.download_url },
+          { pin: true }
+      );
+  };
+
+  // Newest archived version per mod, offered as the one-click rollback target
+  const rollbackTargets: { [modId: string]: string } = {};
+  [...archivedVersions]
+      .sort((a, b) => b.archived_at - a.archived_at)
+      .forEach(v => {
+          if (!(v.mod_id in rollbackTargets)) rollbackTargets[v.mod_id] = v.version;
+      });
+
+  const rollbackMod = async (modId: string) => {
+      const version = rollbackTargets[modId];
+      if (!beamUserPath || !version) return;
+
+      try {
+          setStatus(`Rolling back ${modId}...`);
+          await invoke("rollback_mod", { modId, version });
+          setManifest(await loadManifest());
+          addToast(`Rolled back to ${version}`, 'success');
+          await rescanInstalledMods();
+      } catch (err) {
+          addToast(`Rollback failed: ${err}`, 'error');
+      } finally {
+          setStatus("Ready");
+      }
+  };
+
   const copyModList = async () => {
       try {
         // Get repo mods
@@ -759,6 +614,200 @@ function App() {
       }
   };
 
+  const exportModpack = async () => {
+      try {
+          const path = await invoke<string | null>("export_modpack", { name: null });
+          if (path) addToast(`Modpack saved to ${path}`, 'success');
+      } catch (err) {
+          addToast(`Failed to export modpack: ${err}`, 'error');
+      }
+  };
+
+  // Read a modpack and show what applying it would change before touching anything
+  const importModpack = async () => {
+      try {
+          const imported = await invoke<ModpackImport | null>("open_modpack");
+          if (imported) setModpackImport(imported);
+      } catch (err) {
+          addToast(`Failed to read modpack: ${err}`, 'error');
+      }
+  };
+
+  const applyModpack = async (removeExtra: boolean) => {
+      if (!modpackImport) return;
+      setApplyingModpack(true);
+      setStatus("Applying modpack...");
+      try {
+          const result = await invoke<ModpackResult>("apply_modpack", {
+              modpack: modpackImport.modpack,
+              removeExtra,
+          });
+          setManifest(result.manifest.mods);
+          setModChannels(await invoke<Record<string, Channel>>("get_mod_channels"));
+          result.failed.forEach(f => addToast(`${f.mod_id}: ${f.error}`, 'error'));
+          if (result.unknown.length > 0) {
+              addToast(`Skipped, not in any catalog: ${result.unknown.join(", ")}`, 'info');
+          }
+          addToast(`Modpack applied: ${result.installed.length} installed, ${result.removed.length} removed`, result.failed.length || result.unknown.length ? 'info' : 'success');
+          setModpackImport(null);
+          await rescanInstalledMods();
+      } catch (err) {
+          addToast(`Failed to apply modpack: ${err}`, 'error');
+      } finally {
+          setApplyingModpack(false);
+          setStatus("Ready");
+      }
+  };
+
+  // Enable exactly the mods of a profile; the backend reports every file it touched
+  const switchProfile = async (name: string) => {
+      setSwitchingProfile(true);
+      setStatus(`Switching to ${name}...`);
+      try {
+          const report = await invoke<SwitchReport>("switch_profile", { name });
+          setProfiles(await invoke<ProfileStore>("list_profiles"));
+          const enabled = report.changes.filter(c => c.enabled).length;
+          const disabled = report.changes.length - enabled;
+          report.changes.forEach(c => console.log(`[PROFILE] ${c.enabled ? "enabled" : "disabled"} ${c.filename} via ${c.method}`));
+          report.failed.forEach(f => addToast(`${f.filename}: ${f.error}`, 'error'));
+          if (report.missing.length > 0) {
+              addToast(`Not installed: ${report.missing.join(", ")}`, 'info');
+          }
+          addToast(`Switched to ${report.profile}: ${enabled} enabled, ${disabled} disabled`, 'success');
+          await rescanInstalledMods();
+      } catch (err) {
+          addToast(`Failed to switch profile: ${err}`, 'error');
+      } finally {
+          setSwitchingProfile(false);
+          setStatus("Ready");
+      }
+  };
+
+  const captureProfile = async (name: string) => {
+      try {
+          setProfiles(await invoke<ProfileStore>("capture_profile", { name }));
+          addToast(`Saved profile ${name}`, 'success');
+      } catch (err) {
+          addToast(`Failed to save profile: ${err}`, 'error');
+      }
+  };
+
+  const deleteProfile = async (name: string) => {
+      try {
+          setProfiles(await invoke<ProfileStore>("delete_profile", { name }));
+      } catch (err) {
+          addToast(`Failed to delete profile: ${err}`, 'error');
+      }
+  };
+
+  const refreshSaveBackups = async () => {
+      try {
+          setSaveBackups(await invoke<SaveBackup[]>("list_save_backups"));
+      } catch (err) {
+          console.error("Failed to list save backups:", err);
+      }
+  };
+
+  const createSaveBackup = async () => {
+      setSaveBackupBusy(true);
+      try {
+          const backup = await invoke<SaveBackup | null>("create_save_backup", { reason: null });
+          addToast(backup ? `Backed up ${backup.files} save files` : "No career saves found to back up", backup ? 'success' : 'info');
+          await refreshSaveBackups();
+      } catch (err) {
+          addToast(`Save backup failed: ${err}`, 'error');
+      } finally {
+          setSaveBackupBusy(false);
+      }
+  };
+
+  const restoreSaveBackup = async (backup: SaveBackup) => {
+      setSaveBackupBusy(true);
+      try {
+          await invoke("restore_save_backup", { id: backup.id });
+          addToast("Career saves restored", 'success');
+          await refreshSaveBackups();
+      } catch (err) {
+          addToast(`Restore failed: ${err}`, 'error');
+      } finally {
+          setSaveBackupBusy(false);
+      }
+  };
+
+  const pruneSaveBackups = async () => {
+      try {
+          const removed = await invoke<string[]>("prune_save_backups", { keep: 3 });
+          addToast(`Removed ${removed.length} old save backups`, 'success');
+          await refreshSaveBackups();
+      } catch (err) {
+          addToast(`Failed to prune save backups: ${err}`, 'error');
+      }
+  };
+
+  const refreshModsBackups = async () => {
+      try {
+          setModsBackups(await invoke<ModsBackup[]>("list_mods_backups"));
+      } catch (err) {
+          console.error("Failed to list mods backups:", err);
+      }
+  };
+
+  const createModsBackup = async () => {
+      setModsBackupBusy(true);
+      setStatus("Backing up mods folder...");
+      try {
+          const backup = await invoke<ModsBackup>("create_mods_backup", { label: null });
+          addToast(`Backed up ${backup.files.length} mods (${formatBytes(backup.size)})`, 'success');
+          await refreshModsBackups();
+      } catch (err) {
+          addToast(`Mods backup failed: ${err}`, 'error');
+      } finally {
+          setModsBackupBusy(false);
+          setStatus("Ready");
+      }
+  };
+
+  // Show what a restore would change before touching the mods folder
+  const previewModsBackup = async (backup: ModsBackup) => {
+      try {
+          setModsRestorePreview(await invoke<ModsRestorePreview>("preview_mods_backup", { id: backup.id }));
+      } catch (err) {
+          addToast(`Failed to read backup: ${err}`, 'error');
+      }
+  };
+
+  const restoreModsBackup = async () => {
+      if (!modsRestorePreview) return;
+      setModsBackupBusy(true);
+      setStatus("Restoring mods folder...");
+      try {
+          const report = await invoke<ModsRestoreReport>("restore_mods_backup", { id: modsRestorePreview.backup_id });
+          report.failed.forEach(f => addToast(`${f.filename}: ${f.error}`, 'error'));
+          addToast(
+              `Mods restored: ${report.preview.added.length} added, ${report.preview.overwritten.length} overwritten, ${report.preview.removed.length} removed`,
+              report.failed.length ? 'info' : 'success'
+          );
+          setModsRestorePreview(null);
+          setManifest(await loadManifest());
+          await rescanInstalledMods();
+          await refreshModsBackups();
+      } catch (err) {
+          addToast(`Restore failed: ${err}`, 'error');
+      } finally {
+          setModsBackupBusy(false);
+          setStatus("Ready");
+      }
+  };
+
+  const deleteModsBackup = async (backup: ModsBackup) => {
+      try {
+          await invoke("delete_mods_backup", { id: backup.id });
+          await refreshModsBackups();
+      } catch (err) {
+          addToast(`Failed to delete backup: ${err}`, 'error');
+      }
+  };
+
   const confirmDeleteInstalledMod = async () => {
       if (!beamUserPath || !pendingDelete) {
           setPendingDelete(null);
@@ -777,20 +826,10 @@ function App() {
 
       try {
           const fullPath = `${beamUserPath}\\${filename}`;
-          await invoke("delete_old_mod", { filePath: fullPath });
-
-          const newManifest: ModManifest = { ...manifest };
-          delete newManifest[modId];
-          setManifest(newManifest);
-
-          if (manifestFilePath) {
-              await invoke("save_manifest", {
-                  path: manifestFilePath,
-                  content: JSON.stringify(newManifest, null, 2)
-              });
-          } else {
-              console.error("[DELETE] manifestFilePath not set, cannot save manifest");
-          }
+          await deleteModFile(fullPath, { modId, version: manifest[modId]?.version }, { scheduleIfLocked: true });
+
+          const saved = await invoke<Manifest>("remove_manifest_entry", { modId });
+          setManifest(saved.mods);
 
           addToast(`Removed ${filename}`, 'success');
           await rescanInstalledMods();
@@ -804,13 +843,11 @@ function App() {
 
   const selectFolder = async () => {
       try {
-          const selected = await open({
-              directory: true,
-              multiple: false,
-              title: "Select BeamNG Mods Folder",
-          });
-          
-          if (selected && typeof selected === 'string') {
+          // The backend shows the picker itself and remembers the choice,
+          // so file commands are only ever allowed inside that folder.
+          const selected = await invoke<string | null>("choose_mods_folder");
+
+          if (selected) {
               setBeamUserPath(selected);
               addToast("Folder path updated", 'success');
               // Optionally re-scan the folder
@@ -821,12 +858,95 @@ function App() {
           addToast(`Failed to select folder: ${err}`, 'error');
       }
   };
+  const refreshGameCache = async () => {
+      try {
+          setGameCache(await invoke<GameCacheInfo>("get_game_cache_info"));
+      } catch (err) {
+          console.error("Failed to read the game cache:", err);
+      }
+  };
+
+  const clearGameCache = async () => {
+      try {
+          const report = await invoke<CacheClearReport>("clear_game_cache");
+          report.failed.forEach(f => console.error(`[CACHE] Could not remove ${f.path}: ${f.error}`));
+          addToast(
+              report.failed.length > 0
+                  ? `Freed ${formatBytes(report.freed_bytes)}; ${report.failed.length} entries were in use`
+                  : `Freed ${formatBytes(report.freed_bytes)} of BeamNG cache`,
+              report.failed.length > 0 ? 'info' : 'success'
+          );
+          await refreshGameCache();
+      } catch (err) {
+          addToast(`Failed to clear the game cache: ${err}`, 'error');
+      }
+  };
+
+  const setClearCacheAfterUpdate = async (enabled: boolean) => {
+      try {
+          await invoke("set_clear_cache_after_update", { enabled });
+          await refreshGameCache();
+      } catch (err) {
+          addToast(`Failed to save setting: ${err}`, 'error');
+      }
+  };
+
+  const refreshCatalogSources = async () => {
+      try {
+          setCatalogSources(await invoke<CatalogSource[]>("get_catalog_sources"));
+          setHasGithubToken(await invoke<boolean>("has_github_token"));
+      } catch (err) {
+          console.error("Failed to load catalog sources:", err);
+      }
+  };
+
+  // Save the edited source list and reload the merged catalog from it
+  const saveCatalogSources = async (sources: CatalogSource[]) => {
+      try {
+          setCatalogSources(await invoke<CatalogSource[]>("set_catalog_sources", { sources }));
+          await refreshModsJsonCatalog();
+          setStatus("Ready");
+      } catch (err) {
+          console.error("Failed to save catalog sources:", err);
+          addToast(`Failed to save catalog sources: ${err}`, 'error');
+      }
+  };
+
+  const saveGithubToken = async (token: string | null) => {
+      try {
+          await invoke("set_github_token", { token });
+          setHasGithubToken(await invoke<boolean>("has_github_token"));
+          addToast(token ? "GitHub token saved" : "GitHub token removed", 'success');
+      } catch (err) {
+          console.error("Failed to save GitHub token:", err);
+          addToast(`Failed to save GitHub token: ${err}`, 'error');
+      }
+  };
+
+  const addLocalCatalogSource = async () => {
+      try {
+          const added = await invoke<CatalogSource | null>("add_local_catalog_source");
+          if (added) {
+              await refreshCatalogSources();
+              await refreshModsJsonCatalog();
+              setStatus("Ready");
+          }
+      } catch (err) {
+          console.error("Failed to add catalog file:", err);
+          addToast(`Failed to add catalog file: ${err}`, 'error');
+      }
+  };
+
   return (
     <div className="flex h-screen bg-primary text-primary-text overflow-hidden selection:bg-accent-blue/30" onContextMenu={(e) => e.preventDefault()}>
             <Sidebar
                 activeTab={activeTab}
                 onChangeTab={setActiveTab}
-                onOpenSettings={() => setShowSettingsPopover(true)}
+                onOpenSettings={() => {
+                  setShowSettingsPopover(true);
+                  refreshCatalogSources();
+                  refreshGameCache();
+                }}
             />
 
       {/* Main Content */}
@@ -835,7 +955,7 @@ function App() {
         <HeaderBar onCheckUpdates={() => detectUpdates({ includeUninstalled: false, silent: false })} />
 
         {/* Content Body */}
-        <div className={cn("flex-1 p-8", activeTab === "library" ? "overflow-hidden" : "overflow-y-auto")}>
+        <div className="flex-1 p-8 overflow-y-auto">
             
             <Notifications
               status={status}
@@ -848,10 +968,14 @@ function App() {
               <BrowseView
                 modsConfig={modsConfig}
                 status={status}
-                updates={updates}
+                updates={[...updates, ...catalogUpdates]}
                 installingMods={installingMods}
                 addToast={addToast}
                 installMod={installMod}
+                onShowChangelog={showChangelog}
+                modChannels={modChannels}
+                onSetChannel={setModChannel}
+                onUpdateAll={updateAll}
                 getInstalledForMod={getInstalledForMod}
                 onRetryInit={() => {
                   setStatus("Retrying connection...");
@@ -865,6 +989,46 @@ function App() {
               beamUserPath={beamUserPath}
               onClose={() => setShowSettingsPopover(false)}
               onSelectFolder={selectFolder}
+              catalogSources={catalogSources}
+              onSaveCatalogSources={saveCatalogSources}
+              onAddLocalCatalogSource={addLocalCatalogSource}
+              hasGithubToken={hasGithubToken}
+              onSaveGithubToken={saveGithubToken}
+              gameCache={gameCache}
+              onClearGameCache={clearGameCache}
+              onSetClearCacheAfterUpdate={setClearCacheAfterUpdate}
+            />
+
+            <ChangelogDialog
+              modName={changelogModName}
+              changelog={changelog}
+              loading={!changelog && !changelogError}
+              error={changelogError}
+              onClose={() => setChangelogModName(null)}
+              onOpenUrl={(url) => invoke("open_url_in_browser", { url }).catch((e) => addToast(`${e}`, "error"))}
+            />
+
+            <VersionPickerDialog
+              modName={versionPickerModId ? modsConfig.find((m) => m.id === versionPickerModId)?.name ?? versionPickerModId : null}
+              installedVersion={versionPickerModId ? manifest[versionPickerModId]?.version : undefined}
+              versions={installableVersions}
+              error={versionPickerError}
+              onClose={() => setVersionPickerModId(null)}
+              onInstall={(version) => versionPickerModId && installSpecificVersion(versionPickerModId, version)}
+            />
+
+            <ModpackImportDialog
+              modpackImport={modpackImport}
+              applying={applyingModpack}
+              onCancel={() => setModpackImport(null)}
+              onApply={applyModpack}
+            />
+
+            <ModsRestoreDialog
+              preview={modsRestorePreview}
+              restoring={modsBackupBusy}
+              onCancel={() => setModsRestorePreview(null)}
+              onRestore={restoreModsBackup}
             />
 
             <DeleteConfirmation
@@ -877,10 +1041,41 @@ function App() {
               <InstalledModsTable
                 manifest={manifest}
                 onRequestDelete={(modId, filename) => setPendingDelete({ modId, filename })}
+                rollbackTargets={rollbackTargets}
+                onRollback={rollbackMod}
+                onSetPinned={setModPinned}
+                onChooseVersion={openVersionPicker}
+                onExportModpack={exportModpack}
+                onImportModpack={importModpack}
+                profiles={profiles}
+                switchingProfile={switchingProfile}
+                onSwitchProfile={switchProfile}
+                onCaptureProfile={captureProfile}
+                onDeleteProfile={deleteProfile}
                 onExportList={copyModList}
               />
             )}
 
+            {activeTab === "library" && (
+              <SaveBackupsPanel
+                backups={saveBackups}
+                busy={saveBackupBusy}
+                onCreate={createSaveBackup}
+                onRestore={restoreSaveBackup}
+                onPrune={pruneSaveBackups}
+              />
+            )}
+
+            {activeTab === "library" && (
+              <ModsBackupsPanel
+                backups={modsBackups}
+                busy={modsBackupBusy}
+                onCreate={createModsBackup}
+                onPreview={previewModsBackup}
+                onDelete={deleteModsBackup}
+              />
+            )}
+
         </div>
       </main>
     </div>
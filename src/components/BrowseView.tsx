@@ -1,8 +1,7 @@
 import React from "react";
-import { RefreshCw, LogOut } from "lucide-react";
+import { RefreshCw, LogOut, FileText } from "lucide-react";
 import { cn } from "../utils/cn";
-import type { ModConfig, UpdateInfo, InstalledMod, Toast } from "../types";
-import { isNewerVersion } from "../utils/version";
+import type { ModConfig, UpdateInfo, InstalledMod, Toast, Channel } from "../types";
 
 interface BrowseViewProps {
   modsConfig: ModConfig[];
@@ -11,6 +10,10 @@ interface BrowseViewProps {
   installingMods: { [modId: string]: boolean };
   addToast: (message: string, type?: Toast["type"]) => void;
   installMod: (update: UpdateInfo) => void;
+  onShowChangelog: (mod: ModConfig, update: UpdateInfo) => void;
+  modChannels: Record<string, Channel>;
+  onSetChannel: (mod: ModConfig, channel: Channel) => void;
+  onUpdateAll: (updates: UpdateInfo[]) => void;
   getInstalledForMod: (mod: ModConfig) => InstalledMod | null;
   onRetryInit: () => void;
 }
@@ -22,6 +25,10 @@ export function BrowseView({
   installingMods,
   addToast,
   installMod,
+  onShowChangelog,
+  modChannels,
+  onSetChannel,
+  onUpdateAll,
   getInstalledForMod,
   onRetryInit,
 }: BrowseViewProps) {
@@ -104,25 +111,36 @@ export function BrowseView({
     return badges;
   };
 
+  // Outdated installed mods, once each; pinned mods are left out by the backend
+  const pendingUpdates = modsConfig
+    .filter((mod) => getInstalledForMod(mod))
+    .map((mod) => updates.find((u) => u.modId === mod.id))
+    .filter((u): u is UpdateInfo => !!u);
+  const anyInstalling = Object.values(installingMods).some(Boolean);
+
   return (
     <div className="animate-in fade-in slide-in-from-bottom-4 duration-500 h-full flex flex-col">
-      <h2 className="text-3xl font-bold mb-6 flex-shrink-0">Featured Mods</h2>
+      <div className="flex justify-between items-center mb-6 flex-shrink-0">
+        <h2 className="text-3xl font-bold">Featured Mods</h2>
+        {pendingUpdates.length > 0 && (
+          <button
+            onClick={() => onUpdateAll(pendingUpdates)}
+            disabled={anyInstalling}
+            className="px-4 py-2 rounded-xl text-sm font-medium border-accent border-2 bg-accent/90 hover:bg-accent hover:text-secondary-text transition disabled:opacity-50 disabled:cursor-wait"
+          >
+            Update all ({pendingUpdates.length})
+          </button>
+        )}
+      </div>
 
       <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-x-4 gap-y-6">
         {modsConfig.map((mod) => {
           const installed = getInstalledForMod(mod);
-          let update = updates.find((u) => u.modId === mod.id);
+          const update = updates.find((u) => u.modId === mod.id);
           const isInstalling = !!installingMods[mod.id];
-
-          if (installed && mod.version && !update) {
-            if (isNewerVersion(mod.version, installed.version)) {
-              update = {
-                modId: mod.id,
-                newVersion: mod.version,
-                downloadUrl: mod.directDownload || "",
-              };
-            }
-          }
+          // GitHub pre-releases count as the beta channel
+          const hasBeta = !!mod.channels?.beta || !!mod.githubRepo;
+          const channel = modChannels[mod.id] ?? "stable";
 
           const handleClick = () => {
             if (isInstalling) return;
@@ -178,6 +196,31 @@ export function BrowseView({
                 </div>
               )}
 
+              {hasBeta && (
+                <button
+                  onClick={() => onSetChannel(mod, channel === "beta" ? "stable" : "beta")}
+                  className={cn(
+                    "absolute top-3 left-3 px-2 py-0.5 rounded-lg text-xs font-medium border transition z-20",
+                    channel === "beta"
+                      ? "bg-warning/20 border-warning/40"
+                      : "bg-secondary/80 border-primary-text/20 opacity-0 group-hover:opacity-100"
+                  )}
+                  title={channel === "beta" ? "Following beta builds, click for stable" : "Following stable builds, click for beta"}
+                >
+                  {channel === "beta" ? "Beta" : "Stable"}
+                </button>
+              )}
+
+              {installed && update && (
+                <button
+                  onClick={() => onShowChangelog(mod, update)}
+                  className="absolute top-3 right-3 p-1.5 rounded-lg bg-secondary/80 hover:text-accent opacity-0 group-hover:opacity-100 transition z-20"
+                  title="What's new"
+                >
+                  <FileText size={16} />
+                </button>
+              )}
+
               <div className="absolute left-1/2 bottom-0 w-3/5 -translate-x-1/2 translate-y-[125%] group-hover:translate-y-1/2 opacity-0 group-hover:opacity-100 transition-all duration-300 ease-out z-20">
                 <button
                   onClick={handleClick}
@@ -0,0 +1,64 @@
+import { ExternalLink, X } from "lucide-react";
+import type { Changelog } from "../types";
+
+interface ChangelogDialogProps {
+  // Mod name shown in the title while the changelog is loading
+  modName: string | null;
+  changelog: Changelog | null;
+  loading: boolean;
+  error: string | null;
+  onClose: () => void;
+  onOpenUrl: (url: string) => void;
+}
+
+export function ChangelogDialog({ modName, changelog, loading, error, onClose, onOpenUrl }: ChangelogDialogProps) {
+  if (!modName) return null;
+
+  return (
+    <div className="fixed inset-0 bg-primary/50 z-50 flex items-center justify-center" onClick={onClose}>
+      <div
+        className="bg-secondary/50 backdrop-blur rounded-xl border border-secondary shadow-2xl max-w-2xl w-full mx-4 max-h-[80vh] flex flex-col"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="p-6 pb-3 flex items-start justify-between gap-4">
+          <div>
+            <h2 className="text-xl font-bold">What's new in {modName}</h2>
+            {changelog && (
+              <p className="text-xs opacity-70">
+                {changelog.from_version ? `Since ${changelog.from_version}` : "All versions"}
+                {changelog.to_version ? ` up to ${changelog.to_version}` : ""}
+              </p>
+            )}
+          </div>
+          <button onClick={onClose} className="hover:text-secondary-text transition">
+            <X size={18} />
+          </button>
+        </div>
+
+        <div className="px-6 pb-6 overflow-y-auto space-y-5">
+          {loading && <p className="text-sm opacity-70">Loading release notes...</p>}
+          {error && <p className="text-sm text-error">{error}</p>}
+          {changelog && changelog.entries.length === 0 && (
+            <p className="text-sm opacity-70">No release notes were published for these versions.</p>
+          )}
+          {changelog?.entries.map((entry) => (
+            <div key={entry.version} className="space-y-1">
+              <div className="flex items-center gap-2">
+                <span className="font-semibold">{entry.title || entry.version}</span>
+                {entry.published_at && <span className="text-xs opacity-60">{entry.published_at.slice(0, 10)}</span>}
+                {entry.url && (
+                  <button onClick={() => onOpenUrl(entry.url!)} className="hover:text-accent transition" title="Open release">
+                    <ExternalLink size={14} />
+                  </button>
+                )}
+              </div>
+              <div className="text-sm whitespace-pre-wrap break-words opacity-90">
+                {entry.notes || "No notes for this release."}
+              </div>
+            </div>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
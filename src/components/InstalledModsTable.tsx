@@ -1,13 +1,41 @@
-import { Library, Trash2 } from "lucide-react";
-import type { ModManifest } from "../types";
+import { Download, History, Layers, Library, Pin, PinOff, Trash2, Upload } from "lucide-react";
+import { cn } from "../utils/cn";
+import type { ModManifest, ProfileStore } from "../types";
+import { ProfilesBar } from "./ProfilesBar";
 
 interface InstalledModsTableProps {
   manifest: ModManifest;
   onRequestDelete: (modId: string, filename: string) => void;
+  rollbackTargets: { [modId: string]: string };
+  onRollback: (modId: string) => void;
+  onSetPinned: (modId: string, pinned: boolean) => void;
+  onChooseVersion: (modId: string) => void;
   onExportList: () => void;
+  onExportModpack: () => void;
+  onImportModpack: () => void;
+  profiles: ProfileStore;
+  switchingProfile: boolean;
+  onSwitchProfile: (name: string) => void;
+  onCaptureProfile: (name: string) => void;
+  onDeleteProfile: (name: string) => void;
 }
 
-export function InstalledModsTable({ manifest, onRequestDelete, onExportList }: InstalledModsTableProps) {
+export function InstalledModsTable({
+  manifest,
+  onRequestDelete,
+  rollbackTargets,
+  onRollback,
+  onSetPinned,
+  onChooseVersion,
+  onExportList,
+  onExportModpack,
+  onImportModpack,
+  profiles,
+  switchingProfile,
+  onSwitchProfile,
+  onCaptureProfile,
+  onDeleteProfile,
+}: InstalledModsTableProps) {
   const entries = Object.entries(manifest);
 
   return (
@@ -15,6 +43,13 @@ export function InstalledModsTable({ manifest, onRequestDelete, onExportList }:
       <div className="flex justify-between items-center mb-6">
         <h2 className="text-3xl font-bold">Installed Mods</h2>
       </div>
+      <ProfilesBar
+        store={profiles}
+        switching={switchingProfile}
+        onSwitch={onSwitchProfile}
+        onCapture={onCaptureProfile}
+        onDelete={onDeleteProfile}
+      />
       <div className="bg-secondary rounded-xl flex flex-col max-h-[75vh] overflow-hidden">
         <div className="flex-1 overflow-y-auto">
           <table className="w-full text-left">
@@ -22,6 +57,38 @@ export function InstalledModsTable({ manifest, onRequestDelete, onExportList }:
               {entries.map(([modId, data]) => (
                 <tr key={modId} className="group hover:text-secondary-text transition-colors">
                   <td className="px-6 py-4 font-medium ">{data.filename}</td>
+                  <td className="px-2 py-4 text-right align-middle w-12">
+                    <button
+                      onClick={() => onSetPinned(modId, !data.pinned)}
+                      className={cn(
+                        "transition text-primary-text",
+                        data.pinned ? "text-accent hover:text-primary-text" : "opacity-0 group-hover:opacity-100 hover:text-accent"
+                      )}
+                      title={data.pinned ? `Pinned at ${data.version}, click to unpin` : "Pin this version"}
+                    >
+                      {data.pinned ? <Pin size={16} /> : <PinOff size={16} />}
+                    </button>
+                  </td>
+                  <td className="px-2 py-4 text-right align-middle w-12">
+                    <button
+                      onClick={() => onChooseVersion(modId)}
+                      className="opacity-0 group-hover:opacity-100 transition text-primary-text hover:text-accent"
+                      title="Install another version"
+                    >
+                      <Layers size={16} />
+                    </button>
+                  </td>
+                  <td className="px-2 py-4 text-right align-middle w-12">
+                    {rollbackTargets[modId] && (
+                      <button
+                        onClick={() => onRollback(modId)}
+                        className="opacity-0 group-hover:opacity-100 transition text-primary-text hover:text-accent"
+                        title={`Roll back to ${rollbackTargets[modId]}`}
+                      >
+                        <History size={16} />
+                      </button>
+                    )}
+                  </td>
                   <td className="px-4 py-4 text-right align-middle w-12">
                     <button
                       onClick={() => onRequestDelete(modId, data.filename)}
@@ -47,13 +114,29 @@ export function InstalledModsTable({ manifest, onRequestDelete, onExportList }:
           <span>
             {entries.length} non-repo mod{entries.length !== 1 ? "s" : ""} installed
           </span>
-          <button
-            onClick={onExportList}
-            className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
-          >
-            <Library size={16} />
-            Export List
-          </button>
+          <div className="flex items-center">
+            <button
+              onClick={onImportModpack}
+              className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+            >
+              <Download size={16} />
+              Import Modpack
+            </button>
+            <button
+              onClick={onExportModpack}
+              className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+            >
+              <Upload size={16} />
+              Export Modpack
+            </button>
+            <button
+              onClick={onExportList}
+              className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+            >
+              <Library size={16} />
+              Export List
+            </button>
+          </div>
         </div>
       </div>
     </div>
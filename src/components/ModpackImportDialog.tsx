@@ -0,0 +1,93 @@
+import { useState } from "react";
+import { Download } from "lucide-react";
+import type { ModpackImport } from "../types";
+
+interface ModpackImportDialogProps {
+  modpackImport: ModpackImport | null;
+  applying: boolean;
+  onCancel: () => void;
+  onApply: (removeExtra: boolean) => void;
+}
+
+export function ModpackImportDialog({ modpackImport, applying, onCancel, onApply }: ModpackImportDialogProps) {
+  const [removeExtra, setRemoveExtra] = useState(false);
+  if (!modpackImport) return null;
+
+  const { modpack, plan } = modpackImport;
+
+  return (
+    <div className="fixed inset-0 bg-primary/50 z-50 flex items-center justify-center" onClick={applying ? undefined : onCancel}>
+      <div
+        className="bg-secondary/50 backdrop-blur rounded-xl border border-secondary shadow-2xl max-w-xl w-full mx-4 max-h-[80vh] flex flex-col"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="p-6 pb-3">
+          <h2 className="text-xl font-bold">Import {modpack.name || "modpack"}</h2>
+          <p className="text-xs opacity-70 break-all">{modpackImport.path}</p>
+        </div>
+
+        <div className="px-6 overflow-y-auto space-y-4 text-sm">
+          {plan.install.length > 0 && (
+            <div>
+              <h3 className="font-semibold mb-1">Install</h3>
+              {plan.install.map((change) => (
+                <div key={change.mod_id} className="flex justify-between">
+                  <span>{change.name}</span>
+                  <span className="font-mono opacity-80">
+                    {change.installed_version ? `${change.installed_version} → ` : ""}
+                    {change.version}
+                  </span>
+                </div>
+              ))}
+            </div>
+          )}
+          {plan.remove.length > 0 && (
+            <div>
+              <label className="flex items-center gap-2 font-semibold mb-1">
+                <input type="checkbox" checked={removeExtra} onChange={(e) => setRemoveExtra(e.target.checked)} />
+                Remove mods the modpack does not contain
+              </label>
+              {plan.remove.map((change) => (
+                <div key={change.mod_id} className={removeExtra ? "text-error" : "opacity-60"}>
+                  {change.name} {change.version}
+                </div>
+              ))}
+            </div>
+          )}
+          {plan.unchanged.length > 0 && <p className="opacity-70">{plan.unchanged.length} mod(s) already match.</p>}
+          {plan.unknown.length > 0 && (
+            <p className="text-error">Not in any enabled catalog, skipped: {plan.unknown.join(", ")}</p>
+          )}
+          {plan.missing_files.length > 0 && (
+            <div>
+              <h3 className="font-semibold mb-1">Copy these files into the mods folder by hand</h3>
+              {plan.missing_files.map((file) => (
+                <div key={file} className="font-mono break-all opacity-80">
+                  {file}
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+
+        <div className="flex justify-end gap-3 p-6 pt-4">
+          <button
+            onClick={() => onApply(removeExtra)}
+            disabled={applying}
+            className="text-primary-text hover:text-accent px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition disabled:opacity-50"
+          >
+            <Download size={16} />
+            {applying ? "Applying..." : "Apply"}
+          </button>
+          <button
+            onClick={onCancel}
+            disabled={applying}
+            className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+          >
+            Cancel
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
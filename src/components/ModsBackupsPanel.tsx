@@ -0,0 +1,77 @@
+import { useState } from "react";
+import { Archive, RotateCcw, Trash2 } from "lucide-react";
+import type { ModsBackup } from "../types";
+import { formatBytes } from "../utils/formatBytes";
+
+interface ModsBackupsPanelProps {
+  backups: ModsBackup[];
+  busy: boolean;
+  onCreate: () => void;
+  onPreview: (backup: ModsBackup) => void;
+  onDelete: (backup: ModsBackup) => void;
+}
+
+export function ModsBackupsPanel({ backups, busy, onCreate, onPreview, onDelete }: ModsBackupsPanelProps) {
+  // Deleting a backup cannot be undone, so it takes a second click
+  const [confirming, setConfirming] = useState<string | null>(null);
+
+  return (
+    <div className="bg-secondary rounded-xl mt-6">
+      <div className="flex justify-between items-center px-6 py-3 border-b border-secondary/30">
+        <h3 className="font-semibold">Mods folder backups</h3>
+        <button
+          onClick={onCreate}
+          disabled={busy}
+          className="hover:text-secondary-text px-3 py-1.5 rounded-lg text-sm font-medium flex items-center gap-2 transition disabled:opacity-50"
+          title="Back up every mod zip, db.json and the installer manifest"
+        >
+          <Archive size={16} />
+          {busy ? "Working..." : "Back up mods"}
+        </button>
+      </div>
+      <div className="max-h-48 overflow-y-auto divide-y divide-secondary/30 text-sm">
+        {backups.map((backup) => (
+          <div key={backup.id} className="group flex items-center justify-between px-6 py-2 gap-4">
+            <div>
+              <div className="font-medium">{new Date(backup.created_at * 1000).toLocaleString()}</div>
+              <div className="text-xs opacity-70">
+                {backup.label || "Manual backup"} · {backup.files.length} mods · {formatBytes(backup.size)}
+              </div>
+            </div>
+            <div className="flex items-center gap-4 opacity-0 group-hover:opacity-100 transition">
+              <button
+                onClick={() => onPreview(backup)}
+                disabled={busy}
+                className="hover:text-accent flex items-center gap-2"
+                title="Show what restoring this backup would change"
+              >
+                <RotateCcw size={16} />
+                Restore...
+              </button>
+              <button
+                onClick={() => {
+                  if (confirming === backup.id) {
+                    setConfirming(null);
+                    onDelete(backup);
+                  } else {
+                    setConfirming(backup.id);
+                  }
+                }}
+                onMouseLeave={() => setConfirming(null)}
+                disabled={busy}
+                className={confirming === backup.id ? "text-error flex items-center gap-2" : "hover:text-error flex items-center gap-2"}
+                title="Delete this backup"
+              >
+                <Trash2 size={16} />
+                {confirming === backup.id && "Click again to delete"}
+              </button>
+            </div>
+          </div>
+        ))}
+        {backups.length === 0 && (
+          <div className="px-6 py-4 opacity-70">No backups yet. Back up your mods before trying out a new setup.</div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,81 @@
+import { RotateCcw } from "lucide-react";
+import type { ModsRestorePreview } from "../types";
+
+interface ModsRestoreDialogProps {
+  preview: ModsRestorePreview | null;
+  restoring: boolean;
+  onCancel: () => void;
+  onRestore: () => void;
+}
+
+function FileList({ title, files, className }: { title: string; files: string[]; className?: string }) {
+  if (files.length === 0) return null;
+  return (
+    <div>
+      <h3 className="font-semibold mb-1">
+        {title} ({files.length})
+      </h3>
+      {files.map((file) => (
+        <div key={file} className={`font-mono break-all ${className ?? "opacity-80"}`}>
+          {file}
+        </div>
+      ))}
+    </div>
+  );
+}
+
+export function ModsRestoreDialog({ preview, restoring, onCancel, onRestore }: ModsRestoreDialogProps) {
+  if (!preview) return null;
+
+  const nothingChanges =
+    preview.added.length === 0 && preview.removed.length === 0 && preview.overwritten.length === 0 && preview.inactive.length === 0;
+
+  return (
+    <div className="fixed inset-0 bg-primary/50 z-50 flex items-center justify-center" onClick={restoring ? undefined : onCancel}>
+      <div
+        className="bg-secondary/50 backdrop-blur rounded-xl border border-secondary shadow-2xl max-w-xl w-full mx-4 max-h-[80vh] flex flex-col"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="p-6 pb-3">
+          <h2 className="text-xl font-bold">Restore mods backup</h2>
+          <p className="text-xs opacity-70">
+            The current mods folder is backed up first. Removed and replaced zips go to the installer trash.
+          </p>
+        </div>
+
+        <div className="px-6 overflow-y-auto space-y-4 text-sm">
+          <FileList title="Added" files={preview.added} />
+          <FileList title="Overwritten" files={preview.overwritten} className="text-accent" />
+          <FileList title="Removed" files={preview.removed} className="text-error" />
+          <FileList title="Back to inactive_mods" files={preview.inactive} />
+          {preview.unchanged.length > 0 && <p className="opacity-70">{preview.unchanged.length} zip(s) already match.</p>}
+          {nothingChanges && <p className="opacity-70">No mod zips change.</p>}
+          <p className="opacity-70">
+            {[preview.restores_db && "db.json", preview.restores_manifest && "the installer manifest"]
+              .filter(Boolean)
+              .join(" and ") || "Nothing else"}{" "}
+            will be restored as well.
+          </p>
+        </div>
+
+        <div className="flex justify-end gap-3 p-6 pt-4">
+          <button
+            onClick={onRestore}
+            disabled={restoring}
+            className="text-primary-text hover:text-accent px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition disabled:opacity-50"
+          >
+            <RotateCcw size={16} />
+            {restoring ? "Restoring..." : "Restore"}
+          </button>
+          <button
+            onClick={onCancel}
+            disabled={restoring}
+            className="hover:text-secondary-text px-4 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+          >
+            Cancel
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
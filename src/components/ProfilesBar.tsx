@@ -0,0 +1,74 @@
+import { useState } from "react";
+import { Save, Shuffle, Trash2 } from "lucide-react";
+import type { ProfileStore } from "../types";
+
+interface ProfilesBarProps {
+  store: ProfileStore;
+  switching: boolean;
+  onSwitch: (name: string) => void;
+  onCapture: (name: string) => void;
+  onDelete: (name: string) => void;
+}
+
+export function ProfilesBar({ store, switching, onSwitch, onCapture, onDelete }: ProfilesBarProps) {
+  const [selected, setSelected] = useState<string>(store.active ?? "");
+  const [newName, setNewName] = useState("");
+  const current = selected || store.active || store.profiles[0]?.name || "";
+
+  return (
+    <div className="flex flex-wrap items-center gap-2 mb-4 text-sm">
+      <span className="font-medium">Profile</span>
+      <select
+        value={current}
+        onChange={(e) => setSelected(e.target.value)}
+        className="bg-secondary rounded-lg px-3 py-1.5 border border-primary-text/20"
+      >
+        {store.profiles.length === 0 && <option value="">No profiles yet</option>}
+        {store.profiles.map((profile) => (
+          <option key={profile.name} value={profile.name}>
+            {profile.name}
+            {profile.name === store.active ? " (active)" : ""}
+          </option>
+        ))}
+      </select>
+      <button
+        onClick={() => onSwitch(current)}
+        disabled={!current || switching}
+        className="hover:text-accent px-3 py-1.5 rounded-lg font-medium flex items-center gap-2 transition disabled:opacity-50"
+        title="Enable this profile's mods and disable the rest"
+      >
+        <Shuffle size={16} />
+        {switching ? "Switching..." : "Switch"}
+      </button>
+      <button
+        onClick={() => onDelete(current)}
+        disabled={!current || switching}
+        className="hover:text-error px-2 py-1.5 rounded-lg transition disabled:opacity-50"
+        title="Delete profile"
+      >
+        <Trash2 size={16} />
+      </button>
+      <div className="flex items-center gap-2 ml-auto">
+        <input
+          value={newName}
+          onChange={(e) => setNewName(e.target.value)}
+          placeholder="New profile name"
+          className="bg-secondary rounded-lg px-3 py-1.5 border border-primary-text/20"
+        />
+        <button
+          onClick={() => {
+            onCapture(newName.trim());
+            setSelected(newName.trim());
+            setNewName("");
+          }}
+          disabled={!newName.trim() || switching}
+          className="hover:text-accent px-3 py-1.5 rounded-lg font-medium flex items-center gap-2 transition disabled:opacity-50"
+          title="Save the mods that are enabled now as a profile"
+        >
+          <Save size={16} />
+          Save current
+        </button>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,83 @@
+import { useState } from "react";
+import { Archive, RotateCcw } from "lucide-react";
+import type { SaveBackup } from "../types";
+import { formatBytes } from "../utils/formatBytes";
+
+interface SaveBackupsPanelProps {
+  backups: SaveBackup[];
+  busy: boolean;
+  onCreate: () => void;
+  onRestore: (backup: SaveBackup) => void;
+  onPrune: () => void;
+}
+
+export function SaveBackupsPanel({ backups, busy, onCreate, onRestore, onPrune }: SaveBackupsPanelProps) {
+  // Restoring replaces the current saves, so it takes a second click
+  const [confirming, setConfirming] = useState<string | null>(null);
+
+  return (
+    <div className="bg-secondary rounded-xl mt-6">
+      <div className="flex justify-between items-center px-6 py-3 border-b border-secondary/30">
+        <h3 className="font-semibold">Career save backups</h3>
+        <div className="flex items-center text-sm">
+          {backups.length > 3 && (
+            <button
+              onClick={onPrune}
+              disabled={busy}
+              className="hover:text-secondary-text px-3 py-1.5 rounded-lg font-medium transition disabled:opacity-50"
+              title="Keep the 3 newest backups"
+            >
+              Prune
+            </button>
+          )}
+          <button
+            onClick={onCreate}
+            disabled={busy}
+            className="hover:text-secondary-text px-3 py-1.5 rounded-lg font-medium flex items-center gap-2 transition disabled:opacity-50"
+          >
+            <Archive size={16} />
+            Back up now
+          </button>
+        </div>
+      </div>
+      <div className="max-h-48 overflow-y-auto divide-y divide-secondary/30 text-sm">
+        {backups.map((backup) => (
+          <div key={backup.id} className="group flex items-center justify-between px-6 py-2 gap-4">
+            <div>
+              <div className="font-medium">{new Date(backup.created_at * 1000).toLocaleString()}</div>
+              <div className="text-xs opacity-70">
+                {backup.reason || "Manual backup"} · {backup.files} files · {formatBytes(backup.size)}
+              </div>
+            </div>
+            <button
+              onClick={() => {
+                if (confirming === backup.id) {
+                  setConfirming(null);
+                  onRestore(backup);
+                } else {
+                  setConfirming(backup.id);
+                }
+              }}
+              onMouseLeave={() => setConfirming(null)}
+              disabled={busy}
+              className={
+                confirming === backup.id
+                  ? "transition text-error flex items-center gap-2"
+                  : "opacity-0 group-hover:opacity-100 transition hover:text-accent flex items-center gap-2"
+              }
+              title="Replace the current saves with this backup; they are backed up first"
+            >
+              <RotateCcw size={16} />
+              {confirming === backup.id ? "Click again to restore" : "Restore"}
+            </button>
+          </div>
+        ))}
+        {backups.length === 0 && (
+          <div className="px-6 py-4 opacity-70">
+            No backups yet. Saves are backed up automatically before a core mod is updated.
+          </div>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -1,15 +1,89 @@
-import { X, Folder } from "lucide-react";
+import { useState } from "react";
+import { X, Folder, ArrowUp, ArrowDown, Trash2, Plus, FileText, Eraser } from "lucide-react";
+import type { CatalogSource, GameCacheInfo } from "../types";
+import { formatBytes } from "../utils/formatBytes";
 
 interface SettingsPopoverProps {
   open: boolean;
   beamUserPath: string;
   onClose: () => void;
   onSelectFolder: () => void;
+  catalogSources: CatalogSource[];
+  onSaveCatalogSources: (sources: CatalogSource[]) => void;
+  onAddLocalCatalogSource: () => void;
+  hasGithubToken: boolean;
+  onSaveGithubToken: (token: string | null) => void;
+  gameCache: GameCacheInfo | null;
+  onClearGameCache: () => void;
+  onSetClearCacheAfterUpdate: (enabled: boolean) => void;
 }
 
-export function SettingsPopover({ open, beamUserPath, onClose, onSelectFolder }: SettingsPopoverProps) {
+// Renumber priorities to follow the list order
+const withPriorities = (sources: CatalogSource[]) =>
+  sources.map((source, index) => ({ ...source, priority: index * 10 }));
+
+const sourceIdFromUrl = (url: string, sources: CatalogSource[]) => {
+  let base = "remote";
+  try {
+    base = new URL(url).hostname.toLowerCase().replace(/[^a-z0-9-]/g, "_") || base;
+  } catch {
+    // Invalid URLs are rejected by the backend
+  }
+  let id = base;
+  for (let n = 2; sources.some((s) => s.id === id); n++) id = `${base}-${n}`;
+  return id;
+};
+
+export function SettingsPopover({
+  open,
+  beamUserPath,
+  onClose,
+  onSelectFolder,
+  catalogSources,
+  onSaveCatalogSources,
+  onAddLocalCatalogSource,
+  hasGithubToken,
+  onSaveGithubToken,
+  gameCache,
+  onClearGameCache,
+  onSetClearCacheAfterUpdate,
+}: SettingsPopoverProps) {
+  const [newSourceUrl, setNewSourceUrl] = useState("");
+  const [githubToken, setGithubToken] = useState("");
+
   if (!open) return null;
 
+  const move = (index: number, offset: number) => {
+    const next = [...catalogSources];
+    const [source] = next.splice(index, 1);
+    next.splice(index + offset, 0, source);
+    onSaveCatalogSources(withPriorities(next));
+  };
+
+  const toggle = (index: number) => {
+    onSaveCatalogSources(
+      catalogSources.map((source, i) => (i === index ? { ...source, enabled: !source.enabled } : source))
+    );
+  };
+
+  const remove = (index: number) => {
+    onSaveCatalogSources(catalogSources.filter((_, i) => i !== index));
+  };
+
+  const addUrl = () => {
+    const url = newSourceUrl.trim();
+    if (!url) return;
+    const source: CatalogSource = {
+      id: sourceIdFromUrl(url, catalogSources),
+      name: url,
+      location: url,
+      priority: 0,
+      enabled: true,
+    };
+    onSaveCatalogSources(withPriorities([...catalogSources, source]));
+    setNewSourceUrl("");
+  };
+
   return (
     <div className="fixed inset-0 bg-primary/50 z-50 flex items-center justify-center" onClick={onClose}>
       <div
@@ -22,7 +96,7 @@ export function SettingsPopover({ open, beamUserPath, onClose, onSelectFolder }:
             <X size={20} />
           </button>
         </div>
-        <div className="p-6">
+        <div className="p-6 space-y-6">
           <div>
             <label className="block text-sm font-medium mx-2.5">Path to BeamNG Mods folder</label>
             <div className="flex justify-between items-center">
@@ -41,6 +115,126 @@ export function SettingsPopover({ open, beamUserPath, onClose, onSelectFolder }:
               </button>
             </div>
           </div>
+
+          <div>
+            <label className="block text-sm font-medium mx-2.5 mb-2">Mod catalogs</label>
+            <ul className="space-y-1">
+              {catalogSources.map((source, index) => (
+                <li key={source.id} className="flex items-center gap-2 px-2.5 py-1 text-sm">
+                  <input type="checkbox" checked={source.enabled} onChange={() => toggle(index)} />
+                  <div className="flex-1 min-w-0">
+                    <div className="font-medium truncate">{source.name || source.id}</div>
+                    <div className="text-xs text-primary-text/60 font-mono truncate">
+                      {source.location || "Default RLS catalog"}
+                    </div>
+                  </div>
+                  <button
+                    onClick={() => move(index, -1)}
+                    disabled={index === 0}
+                    className="hover:text-primary-text disabled:opacity-30 transition"
+                    title="Load earlier"
+                  >
+                    <ArrowUp size={14} />
+                  </button>
+                  <button
+                    onClick={() => move(index, 1)}
+                    disabled={index === catalogSources.length - 1}
+                    className="hover:text-primary-text disabled:opacity-30 transition"
+                    title="Load later"
+                  >
+                    <ArrowDown size={14} />
+                  </button>
+                  <button
+                    onClick={() => remove(index)}
+                    disabled={source.id === "official"}
+                    className="hover:text-error disabled:opacity-30 transition"
+                    title="Remove"
+                  >
+                    <Trash2 size={14} />
+                  </button>
+                </li>
+              ))}
+            </ul>
+            <div className="flex items-center gap-2 mt-2">
+              <input
+                type="url"
+                value={newSourceUrl}
+                onChange={(e) => setNewSourceUrl(e.target.value)}
+                onKeyDown={(e) => e.key === "Enter" && addUrl()}
+                placeholder="https://example.com/mods.json"
+                className="flex-1 text-primary-text/80 px-3 py-2 text-sm font-mono"
+              />
+              <button
+                onClick={addUrl}
+                className="hover:text-primary-text px-3 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+              >
+                <Plus size={16} />
+                Add URL
+              </button>
+              <button
+                onClick={onAddLocalCatalogSource}
+                className="hover:text-primary-text px-3 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+              >
+                <FileText size={16} />
+                Add file
+              </button>
+            </div>
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium mx-2.5">BeamNG cache</label>
+            <p className="text-xs text-primary-text/60 mx-2.5 mb-2">
+              The game's temp and cache folders can keep files of a replaced mod alive. BeamNG rebuilds them on the next
+              start.
+            </p>
+            <div className="flex items-center gap-2 mx-2.5 text-sm">
+              <label className="flex-1 flex items-center gap-2">
+                <input
+                  type="checkbox"
+                  checked={!!gameCache?.clear_after_update}
+                  onChange={(e) => onSetClearCacheAfterUpdate(e.target.checked)}
+                />
+                Clear after updating a mod
+              </label>
+              <span className="text-xs text-primary-text/60">
+                {gameCache ? formatBytes(gameCache.dirs.reduce((sum, dir) => sum + dir.bytes, 0)) : ""}
+              </span>
+              <button
+                onClick={onClearGameCache}
+                disabled={!gameCache || gameCache.dirs.length === 0}
+                className="hover:text-primary-text disabled:opacity-30 px-3 py-2 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+              >
+                <Eraser size={16} />
+                Clear now
+              </button>
+            </div>
+          </div>
+
+          <div>
+            <label className="block text-sm font-medium mx-2.5">GitHub token (optional)</label>
+            <p className="text-xs text-primary-text/60 mx-2.5 mb-2">
+              Raises the GitHub rate limit for update checks. A token without any scopes is enough.
+            </p>
+            <div className="flex items-center gap-2">
+              <input
+                type="password"
+                value={githubToken}
+                onChange={(e) => setGithubToken(e.target.value)}
+                placeholder={hasGithubToken ? "Token saved" : "ghp_..."}
+                className="flex-1 text-primary-text/80 px-3 py-2 text-sm font-mono"
+              />
+              <button
+                onClick={() => {
+                  onSaveGithubToken(githubToken.trim() || null);
+                  setGithubToken("");
+                }}
+                disabled={!githubToken.trim() && !hasGithubToken}
+                className="hover:text-primary-text disabled:opacity-30 px-3 py-2 rounded-lg text-sm font-medium transition"
+              >
+                {githubToken.trim() || !hasGithubToken ? "Save" : "Remove"}
+              </button>
+            </div>
+          </div>
         </div>
       </div>
     </div>
@@ -0,0 +1,63 @@
+import { Download, X } from "lucide-react";
+import type { InstallableVersion } from "../types";
+
+interface VersionPickerDialogProps {
+  modName: string | null;
+  installedVersion?: string;
+  // Null while the list is loading
+  versions: InstallableVersion[] | null;
+  error: string | null;
+  onClose: () => void;
+  onInstall: (version: InstallableVersion) => void;
+}
+
+export function VersionPickerDialog({ modName, installedVersion, versions, error, onClose, onInstall }: VersionPickerDialogProps) {
+  if (!modName) return null;
+
+  return (
+    <div className="fixed inset-0 bg-primary/50 z-50 flex items-center justify-center" onClick={onClose}>
+      <div
+        className="bg-secondary/50 backdrop-blur rounded-xl border border-secondary shadow-2xl max-w-xl w-full mx-4 max-h-[80vh] flex flex-col"
+        onClick={(e) => e.stopPropagation()}
+      >
+        <div className="p-6 pb-3 flex items-start justify-between gap-4">
+          <div>
+            <h2 className="text-xl font-bold">Install a version of {modName}</h2>
+            <p className="text-xs opacity-70">The installed version is pinned so updates leave it alone.</p>
+          </div>
+          <button onClick={onClose} className="hover:text-secondary-text transition">
+            <X size={18} />
+          </button>
+        </div>
+
+        <div className="px-6 pb-6 overflow-y-auto divide-y divide-secondary/30">
+          {!versions && !error && <p className="text-sm opacity-70">Loading versions...</p>}
+          {error && <p className="text-sm text-error">{error}</p>}
+          {versions && versions.length === 0 && <p className="text-sm opacity-70">No installable versions found.</p>}
+          {versions?.map((version) => (
+            <div key={version.version} className="flex items-center justify-between py-2 gap-3">
+              <div className="flex items-center gap-2">
+                <span className="font-mono">{version.version}</span>
+                {version.prerelease && (
+                  <span className="px-2 py-0.5 rounded text-xs border bg-warning/20 border-warning/30">Pre-release</span>
+                )}
+                {version.published_at && <span className="text-xs opacity-60">{version.published_at.slice(0, 10)}</span>}
+              </div>
+              {version.version === installedVersion ? (
+                <span className="text-xs opacity-70 px-3">Installed</span>
+              ) : (
+                <button
+                  onClick={() => onInstall(version)}
+                  className="hover:text-accent px-3 py-1 rounded-lg text-sm font-medium flex items-center gap-2 transition"
+                >
+                  <Download size={14} />
+                  Install
+                </button>
+              )}
+            </div>
+          ))}
+        </div>
+      </div>
+    </div>
+  );
+}
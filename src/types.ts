@@ -6,6 +6,8 @@ export interface Toast {
   type: "error" | "success" | "info";
 }
 
+export type Channel = "stable" | "beta";
+
 export interface ModConfig {
   id: string;
   name: string;
@@ -13,32 +15,376 @@ export interface ModConfig {
   githubRepo?: string;
   directDownload?: string;
   version?: string;
+  // Builds on other release channels; `version`/`directDownload` are stable
+  channels?: Partial<Record<Channel, { version: string; directDownload?: string }>>;
+  // Earlier releases that can still be installed
+  versions?: { version: string; directDownload?: string }[];
   imageUrl?: string;
   assetPattern?: string;
   category?: "core" | "map" | "vehicle";
   state?: "Public" | "Beta";
+  // Markdown changelog or JSON list of release notes; GitHub releases are
+  // used when unset
+  changelogUrl?: string;
+  // Mods installed together with this one, e.g. the career core for a map
+  dependencies?: (string | { id: string; version?: string })[];
+  // Mods that cannot be installed at the same time
+  conflicts?: string[];
+  // Catalog source the entry came from; ids from sources other than
+  // "official" are namespaced as `source:id`
+  origin?: string;
 }
 
 export interface InstalledMod {
+  id?: string;
   version: string;
   filename: string;
+  source_url?: string | null;
+  sha256?: string | null;
+  size?: number | null;
+  installed_at?: number | null;
+  installed_by_installer?: boolean;
+  // Kept at this version by update checks and dependency installs
+  pinned?: boolean;
 }
 
 export interface ModManifest {
   [modId: string]: InstalledMod;
 }
 
+// Document returned by the backend manifest commands
+export interface Manifest {
+  schema_version: number;
+  mods: ModManifest;
+}
+
+export interface LoadedManifest extends Manifest {
+  // Set when the manifest file was damaged and restored from this backup,
+  // or "empty" when no backup could be read and the list was reset
+  recovered_from?: string | null;
+}
+
+export interface VersionChange {
+  mod_id: string;
+  filename: string;
+  from: string;
+  to: string;
+}
+
+export interface AmbiguousMatch {
+  filename: string;
+  candidates: string[];
+  assigned_to: string;
+}
+
+// Result of matching the manifest against the mods folder (`reconcile_mods`)
+export interface Reconciliation {
+  manifest: Manifest;
+  diff: {
+    added: InstalledMod[];
+    removed: InstalledMod[];
+    version_changed: VersionChange[];
+    unmatched: string[];
+    ambiguous: AmbiguousMatch[];
+  };
+  applied: boolean;
+}
+
 export interface UpdateInfo {
   modId: string;
   newVersion: string;
   downloadUrl: string;
   externalUrl?: string;
+  installedVersion?: string | null;
+}
+
+// Latest version of a mod, handed to `check_updates`
+export interface AvailableVersion {
+  modId: string;
+  version: string;
+  downloadUrl?: string | null;
 }
 
 export interface BackendConfig {
   remote_mods_json_url?: string;
 }
 
+// Problem found while validating a catalog entry
+export interface CatalogIssue {
+  source: string;
+  index: number;
+  mod_id?: string | null;
+  field?: string | null;
+  severity: "error" | "warning";
+  message: string;
+}
+
+// A mod catalog to load, as stored in the backend settings
+export interface CatalogSource {
+  id: string;
+  name: string;
+  // http(s) URL or local file; empty for the official source's default URL
+  location: string;
+  // Lower loads and is listed first
+  priority: number;
+  enabled: boolean;
+}
+
+export interface SourceStatus {
+  id: string;
+  name: string;
+  location: string;
+  // null when the source could not be loaded at all
+  loaded_from: "remote" | "cached" | "bundled" | "local" | null;
+  entries: number;
+  fetched_at?: number | null;
+  age_secs?: number | null;
+  error?: string | null;
+}
+
+// Merged catalog returned by `load_catalog`
+export interface LoadedCatalog {
+  mods: ModConfig[];
+  // Entries with an error are left out of `mods`
+  issues: CatalogIssue[];
+  sources: SourceStatus[];
+}
+
+export interface ResolvedRelease {
+  mod_id: string;
+  version: string;
+  tag: string;
+  download_url: string;
+  asset_name: string;
+  size: number;
+  prerelease: boolean;
+  published_at?: string | null;
+  html_url?: string | null;
+}
+
+// Result of `resolve_github_releases` for one catalog entry
+export interface ReleaseLookup {
+  mod_id: string;
+  release?: ResolvedRelease | null;
+  error?: string | null;
+}
+
+export interface PlanStep {
+  mod_id: string;
+  name: string;
+  version?: string | null;
+  download_url?: string | null;
+  installed_version?: string | null;
+  reason: { kind: "requested" } | { kind: "dependency"; of: string; constraint?: string | null };
+}
+
+// Install plan computed by the backend dependency resolver
+export interface InstallPlan {
+  // Dependencies come before the mods that need them
+  steps: PlanStep[];
+  satisfied: {
+    mod_id: string;
+    installed_version: string;
+    required_by: string;
+    constraint?: string | null;
+  }[];
+}
+
+export interface InstallResult {
+  plan: InstallPlan;
+  installed: InstalledMod[];
+  manifest: Manifest;
+  // Set when an update cleared BeamNG's temp/cache folders
+  cache_cleared?: CacheClearReport | null;
+}
+
+export interface CacheDir {
+  path: string;
+  bytes: number;
+  files: number;
+}
+
+export interface GameCacheInfo {
+  dirs: CacheDir[];
+  clear_after_update: boolean;
+}
+
+export interface CacheClearReport {
+  cleared: CacheDir[];
+  freed_bytes: number;
+  failed: { path: string; error: string }[];
+}
+
+// A version of a mod offered by `list_mod_versions`, newest first
+export interface InstallableVersion {
+  version: string;
+  download_url: string;
+  prerelease: boolean;
+  published_at?: string | null;
+}
+
+export interface ModpackMod {
+  id: string;
+  version: string;
+  channel: Channel;
+  pinned: boolean;
+  sha256?: string | null;
+}
+
+// Shareable description of an installed setup
+export interface Modpack {
+  format: "rls-modpack";
+  format_version: number;
+  name?: string | null;
+  created_at?: number | null;
+  mods: ModpackMod[];
+  // Zips the catalog cannot install, copied by hand
+  unmanaged: { filename: string; sha256?: string | null; size?: number | null }[];
+}
+
+export interface ModpackChange {
+  mod_id: string;
+  name: string;
+  installed_version?: string | null;
+  version: string;
+}
+
+// What importing a modpack would change on this machine
+export interface ModpackPlan {
+  install: ModpackChange[];
+  remove: ModpackChange[];
+  unchanged: string[];
+  unknown: string[];
+  missing_files: string[];
+}
+
+export interface ModpackImport {
+  path: string;
+  modpack: Modpack;
+  plan: ModpackPlan;
+}
+
+export interface ModpackResult {
+  installed: string[];
+  removed: string[];
+  failed: { mod_id: string; error: string }[];
+  // Modpack mods in none of the enabled catalogs, skipped
+  unknown: string[];
+  manifest: Manifest;
+}
+
+// Named set of mods to keep enabled
+export interface Profile {
+  name: string;
+  mods: string[];
+  files: string[];
+}
+
+export interface ProfileStore {
+  active?: string | null;
+  profiles: Profile[];
+}
+
+export interface SwitchReport {
+  profile: string;
+  changes: { filename: string; mod_id?: string | null; enabled: boolean; method: "db_json" | "moved" }[];
+  missing: string[];
+  failed: { filename: string; error: string }[];
+}
+
+// Zip of the career save folders in the installer data folder
+export interface SaveBackup {
+  id: string;
+  created_at: number;
+  reason?: string | null;
+  folders: string[];
+  files: number;
+  size: number;
+}
+
+export interface BackedUpFile {
+  filename: string;
+  size: number;
+  sha256: string;
+}
+
+// Zip of the mods folder, BeamNG's db.json and the installer manifest
+export interface ModsBackup {
+  id: string;
+  created_at: number;
+  label?: string | null;
+  files: BackedUpFile[];
+  // Zips parked by a profile in inactive_mods
+  inactive: BackedUpFile[];
+  has_db: boolean;
+  has_manifest: boolean;
+  // Safety backup taken before a restore
+  automatic: boolean;
+  size: number;
+}
+
+// What restoring a mods backup would change in the mods folder
+export interface ModsRestorePreview {
+  backup_id: string;
+  added: string[];
+  removed: string[];
+  overwritten: string[];
+  unchanged: string[];
+  inactive: string[];
+  restores_db: boolean;
+  restores_manifest: boolean;
+}
+
+export interface ModsRestoreReport {
+  preview: ModsRestorePreview;
+  safety_backup: ModsBackup;
+  failed: { filename: string; error: string }[];
+}
+
+export interface ChangelogEntry {
+  version: string;
+  title?: string | null;
+  published_at?: string | null;
+  notes: string;
+  url?: string | null;
+}
+
+// Release notes between the installed and the newest version of a mod
+export interface Changelog {
+  mod_id: string;
+  from_version?: string | null;
+  to_version?: string | null;
+  source: "changelog_url" | "github";
+  // Newest first
+  entries: ChangelogEntry[];
+}
+
+export interface TrashEntry {
+  id: string;
+  filename: string;
+  original_path: string;
+  mod_id?: string;
+  version?: string;
+  trashed_at: number;
+  size: number;
+}
+
+export interface ArchivedVersion {
+  mod_id: string;
+  version: string;
+  filename: string;
+  archived_at: number;
+  size: number;
+}
+
+export type DeleteOutcome =
+  | { status: "deleted"; path: string }
+  | { status: "trashed"; path: string; entry: TrashEntry }
+  | { status: "archived"; path: string; entry: ArchivedVersion }
+  | { status: "already_absent"; path: string }
+  | { status: "locked"; path: string; scheduled: boolean; error: string }
+  | { status: "failed"; path: string; error: string };
+
 export interface DownloadProgressEventPayload {
   mod_id?: string;
   url: string;